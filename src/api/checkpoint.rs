@@ -0,0 +1,38 @@
+use crate::api::models::Comment;
+use crate::error::{RdtError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Progress of a full-thread comment fetch, saved so an interrupted run can resume
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CommentCheckpoint {
+    pub post_id: String,
+    /// Comments fetched so far (flattened, with `replies` cleared)
+    pub fetched: Vec<Comment>,
+    /// Comment ids from "more" stubs that still need fetching
+    pub pending_more: Vec<String>,
+}
+
+impl CommentCheckpoint {
+    /// Load a checkpoint for `post_id`, ignoring state left over from a different post
+    pub fn load(path: &Path, post_id: &str) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(path)?;
+        let checkpoint: Self = serde_json::from_str(&content)
+            .map_err(|e| RdtError::Config(format!("Invalid resume file: {}", e)))?;
+
+        Ok((checkpoint.post_id == post_id).then_some(checkpoint))
+    }
+
+    /// Write atomically so a crash mid-save can't corrupt the previous checkpoint
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string(self)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
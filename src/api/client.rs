@@ -1,50 +1,176 @@
+use crate::api::checkpoint::CommentCheckpoint;
 use crate::api::models::{
-    Comment, CommentSummary, Listing, Post, PostSummary, SearchResults, Subreddit,
-    SubredditSummary, User, UserSummary,
+    parse_comment, Comment, CommentSummary, Listing, NodeBudget, ModeratedSubreddit, ModeratedSummary, Post,
+    PageCursor, PostFilter, PostPage, PostSummary, SearchResults, SubmittedPost, Subreddit, SubredditSearchResults, SubredditSummary,
+    CommentThread, InfoItem, NewPosts, Thing, Traffic, TrafficSummary, Trophy, User, UserSummary,
 };
 use crate::config::Config;
 use crate::error::{RdtError, Result};
 use crate::nlp::router::SearchParams;
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, USER_AGENT};
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::RwLock;
 
 const REDDIT_API_BASE: &str = "https://oauth.reddit.com";
 const REDDIT_PUBLIC_BASE: &str = "https://www.reddit.com";
+// Fixed, unlike the bases above, so a custom data host never receives credentials
+const REDDIT_TOKEN_URL: &str = "https://www.reddit.com/api/v1/access_token";
+
+// Reddit's cap on ids per /api/morechildren request
+const MORE_CHILDREN_BATCH: usize = 100;
+
+// Reddit's cap on fullnames per /by_id request
+pub const BY_ID_BATCH: usize = 100;
+
+// Phrases from Reddit's anti-bot pages ("whoa there, pardner!", network security blocks)
+const BLOCK_PAGE_MARKERS: &[&str] = &[
+    "whoa there, pardner",
+    "blocked by network security",
+    "your request has been blocked",
+];
+
+const BLOCKED_MESSAGE: &str =
+    "blocked — authenticate with 'rdt auth login' or set a proper user-agent";
+
+// Reddit's cap on `limit` for one listing request; larger values are silently ignored.
+// Single-page methods cap `limit` at this without a warning; that's left to callers.
+pub const MAX_LISTING_LIMIT: u32 = 100;
+
+// Most "did you mean" names offered for a subreddit that doesn't exist
+const MAX_SUGGESTIONS: usize = 3;
+
+// Most extra search pages to fetch while topping up filtered results
+const MAX_FILTER_PAGES: usize = 5;
+
+// Retries for a GET that hit a 5xx, or a 429 with a short enough wait
+const MAX_GET_RETRIES: u32 = 2;
+
+// Wait before the first 5xx retry; doubles each time
+const RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+// Longest 429 wait a GET retry will sit through; longer ones surface as RateLimited
+const MAX_RETRY_WAIT_SECS: u64 = 10;
 
 pub struct RedditClient {
     client: reqwest::Client,
     config: Config,
     use_oauth: bool,
+    api_base: String,
+    public_base: String,
+    /// Where refresh tokens are traded; only tests point it away from reddit.com
+    token_url: String,
+    /// Bearer token; replaced in place when it expires and gets refreshed
+    access_token: RwLock<Option<String>>,
+    /// Replaced when Reddit rotates it during a refresh
+    refresh_token: RwLock<Option<String>>,
+    /// Rate-limit headers from the latest response that had them
+    rate_limit: RwLock<Option<RateLimit>>,
+}
+
+/// Reddit's `x-ratelimit-remaining`/`x-ratelimit-reset` headers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Requests left in the current window
+    pub remaining: u32,
+    /// Seconds until the window resets
+    pub reset_secs: u64,
+}
+
+impl RateLimit {
+    /// None unless both headers are present and numeric (`remaining` may be fractional)
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+        Some(Self {
+            remaining: number("x-ratelimit-remaining")?.max(0.0) as u32,
+            reset_secs: number("x-ratelimit-reset")?.max(0.0).ceil() as u64,
+        })
+    }
 }
 
 impl RedditClient {
     pub async fn new() -> Result<Self> {
-        let config = Config::load()?;
+        Self::with_config(Config::load()?)
+    }
+
+    /// Build a client from an already-loaded config
+    pub fn with_config(config: Config) -> Result<Self> {
+        Self::from_config(config, None)
+    }
+
+    /// `base_url_override` replaces the OAuth, public and token hosts (used by tests)
+    fn from_config(config: Config, base_url_override: Option<String>) -> Result<Self> {
         let use_oauth = config.has_credentials() && config.reddit.access_token.is_some();
 
-        let mut headers = HeaderMap::new();
+        // Precedence: explicit override, then env var, then config, then reddit.com
+        let api_base = resolve_base(
+            base_url_override.as_deref(),
+            "RDT_API_BASE_URL",
+            config.reddit.api_base_url.as_deref(),
+            REDDIT_API_BASE,
+        );
+        let public_base = resolve_base(
+            base_url_override.as_deref(),
+            "RDT_PUBLIC_BASE_URL",
+            config.reddit.public_base_url.as_deref(),
+            REDDIT_PUBLIC_BASE,
+        );
+        let token_url = match base_url_override {
+            Some(base) => format!("{}/api/v1/access_token", base.trim_end_matches('/')),
+            None => REDDIT_TOKEN_URL.to_string(),
+        };
+
+        let mut headers = extra_headers(&config)?;
         headers.insert(
             USER_AGENT,
             HeaderValue::from_str(&config.user_agent())
                 .map_err(|e| RdtError::Config(e.to_string()))?,
         );
 
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .build()?;
+        let mut builder = reqwest::Client::builder().default_headers(headers);
+        if let Some(max_idle) = config.http.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(secs) = config.http.pool_idle_timeout_secs {
+            builder = builder.pool_idle_timeout(std::time::Duration::from_secs(secs));
+        }
+        let client = builder.build()?;
 
         Ok(Self {
             client,
+            access_token: RwLock::new(config.reddit.access_token.clone()),
+            refresh_token: RwLock::new(config.reddit.refresh_token.clone()),
+            rate_limit: RwLock::new(None),
             config,
             use_oauth,
+            api_base,
+            public_base,
+            token_url,
         })
     }
 
-    fn base_url(&self) -> &str {
+    /// Fail fast with `NotAuthenticated` for endpoints that only work over OAuth,
+    /// instead of letting the public API answer with a confusing 403
+    pub fn require_auth(&self) -> Result<()> {
         if self.use_oauth {
-            REDDIT_API_BASE
+            Ok(())
         } else {
-            REDDIT_PUBLIC_BASE
+            Err(RdtError::NotAuthenticated)
+        }
+    }
+
+    /// Whether requests go to the OAuth API with the stored token
+    pub fn is_authenticated(&self) -> bool {
+        self.use_oauth
+    }
+
+    /// Host requests are sent to (OAuth or public, whichever is in use)
+    pub fn base_url(&self) -> &str {
+        if self.use_oauth {
+            &self.api_base
+        } else {
+            &self.public_base
         }
     }
 
@@ -52,7 +178,7 @@ impl RedditClient {
         // For public API, we need .json before query params
         // For OAuth API, no .json suffix needed
         let url = if self.use_oauth {
-            format!("{}{}", REDDIT_API_BASE, endpoint)
+            format!("{}{}", self.base_url(), endpoint)
         } else {
             // Split endpoint into path and query string to insert .json correctly
             let (path, query) = if let Some(idx) = endpoint.find('?') {
@@ -60,47 +186,265 @@ impl RedditClient {
             } else {
                 (endpoint, "")
             };
-            format!("{}{}.json{}", REDDIT_PUBLIC_BASE, path, query)
+            format!("{}{}.json{}", self.base_url(), path, query)
         };
 
-        let mut request = self.client.get(&url);
-
-        if self.use_oauth {
-            if let Some(token) = &self.config.reddit.access_token {
-                request = request.bearer_auth(token);
+        // GETs are idempotent, so transient failures get a few bounded retries
+        let mut attempt = 0;
+        loop {
+            let response = self.send(|| self.client.get(&url)).await?;
+            let status = response.status();
+            let wait = if status.is_server_error() {
+                Some(RETRY_BASE_DELAY * 2u32.pow(attempt))
+            } else if status == 429 {
+                rate_limit_reset_secs(response.headers())
+                    .filter(|secs| *secs <= MAX_RETRY_WAIT_SECS)
+                    .map(std::time::Duration::from_secs)
+            } else {
+                None
+            };
+            match wait {
+                Some(wait) if attempt < MAX_GET_RETRIES => {
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                _ => return read_response(response).await,
             }
         }
+    }
+
+    /// Form POST to the OAuth API (with `api_type=json`), for actions such as submitting.
+    /// Requires login.
+    async fn post_form<T: for<'de> Deserialize<'de>>(
+        &self,
+        endpoint: &str,
+        form: &[(&str, &str)],
+    ) -> Result<T> {
+        self.require_auth()?;
+        let url = format!("{}{}", self.api_base, endpoint);
+        let mut form = form.to_vec();
+        form.push(("api_type", "json"));
+
+        let response = self.send(|| self.client.post(&url).form(&form)).await?;
+        read_response(response).await
+    }
+
+    /// Send a request with the bearer token (when logged in). Access tokens expire after
+    /// an hour, so on a 401 swap in a fresh one and resend once.
+    async fn send(&self, build: impl Fn() -> reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let mut response = self.authorize(build()).send().await?;
+
+        let can_refresh = self.use_oauth && self.refresh_token.read().unwrap().is_some();
+        if response.status() == 401 && can_refresh {
+            self.refresh_access_token().await?;
+            response = self.authorize(build()).send().await?;
+        }
+
+        if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+            *self.rate_limit.write().unwrap() = Some(rate_limit);
+        }
+        Ok(response)
+    }
 
-        let response = request.send().await?;
+    /// Rate-limit state from the most recent response that reported it
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.read().unwrap()
+    }
 
-        if response.status() == 429 {
-            return Err(RdtError::RateLimited);
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if !self.use_oauth {
+            return request;
         }
+        match self.access_token.read().unwrap().as_deref() {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
 
+    /// Trade the stored refresh token for a new access token, used for the rest of this
+    /// client's requests and saved to config.toml for later runs
+    pub async fn refresh_access_token(&self) -> Result<()> {
+        let reddit = &self.config.reddit;
+        let refresh_token = self.refresh_token.read().unwrap().clone();
+        let (Some(client_id), Some(refresh_token)) = (&reddit.client_id, refresh_token) else {
+            return Err(RdtError::Auth(
+                "Access token expired and can't be refreshed. Run 'rdt auth login'.".to_string(),
+            ));
+        };
+
+        let response = self
+            .client
+            .post(&self.token_url)
+            .basic_auth(client_id, Some(reddit.client_secret.as_deref().unwrap_or("")))
+            .form(&[("grant_type", "refresh_token"), ("refresh_token", refresh_token.as_str())])
+            .send()
+            .await
+            .map_err(|e| RdtError::Auth(format!("Token refresh failed: {}", e)))?;
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
-            return Err(RdtError::RedditApi(format!(
-                "HTTP {}: {}",
+            return Err(RdtError::Auth(format!(
+                "Token refresh failed (HTTP {}): {}. Run 'rdt auth login'.",
                 status, body
             )));
         }
 
-        // Get the raw text first to debug deserialization issues
-        let text = response.text().await?;
+        let token_data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| RdtError::Auth(format!("Failed to parse token response: {}", e)))?;
+        let access_token = token_data["access_token"]
+            .as_str()
+            .ok_or_else(|| RdtError::Auth("No access_token in response".to_string()))?;
 
-        let data: T = serde_json::from_str(&text).map_err(|e| {
-            RdtError::RedditApi(format!(
-                "JSON parse error: {} (first 500 chars: {})",
-                e,
-                &text[..text.len().min(500)]
-            ))
-        })?;
+        *self.access_token.write().unwrap() = Some(access_token.to_string());
+
+        // Reddit may rotate the refresh token too; the old one can be revoked right away
+        if let Some(rotated) = token_data["refresh_token"].as_str() {
+            *self.refresh_token.write().unwrap() = Some(rotated.to_string());
+        }
+
+        let mut config = self.config.clone();
+        config.reddit.access_token = Some(access_token.to_string());
+        config.reddit.refresh_token = self.refresh_token.read().unwrap().clone();
+        config.save()
+    }
+
+    /// Get the authenticated user's identity (requires OAuth)
+    pub async fn get_me(&self) -> Result<UserSummary> {
+        self.require_auth()?;
+        let user: User = self.get("/api/v1/me").await?;
+        Ok(user.into())
+    }
 
-        Ok(data)
+    /// Cheapest request that proves the API (and, when logged in, the token) works:
+    /// `/api/v1/me` when authenticated, else a one-post listing
+    pub async fn ping(&self) -> Result<()> {
+        if self.use_oauth {
+            self.get_me().await?;
+        } else {
+            self.get::<Listing<Post>>("/r/all/new?limit=1").await?;
+        }
+        Ok(())
     }
 
     pub async fn search(&self, params: &SearchParams) -> Result<SearchResults> {
+        self.search_page(params, &PageCursor::default()).await
+    }
+
+    /// Up to `params.limit` search results starting at `cursor`; follow the returned
+    /// `after`/`before` to move through the listing
+    pub async fn search_page(
+        &self,
+        params: &SearchParams,
+        cursor: &PageCursor,
+    ) -> Result<SearchResults> {
+        let mut results = self.fetch_search_page(params, cursor).await?;
+        // Only `after` can be followed forwards; a `before` request is a single page
+        if cursor.before.is_some() {
+            return Ok(results);
+        }
+
+        // Limits over Reddit's per-request cap are met by following `after`
+        let limit = params.limit as usize;
+        while results.posts.len() < limit {
+            let Some(after) = results.after.take() else {
+                break;
+            };
+            let cursor = PageCursor {
+                after: Some(after),
+                before: None,
+            };
+            let remaining = SearchParams {
+                limit: (limit - results.posts.len()) as u32,
+                ..params.clone()
+            };
+            let page = self.fetch_search_page(&remaining, &cursor).await?;
+            if page.posts.is_empty() {
+                break;
+            }
+            results.posts.extend(page.posts);
+            results.after = page.after;
+        }
+
+        results.posts.truncate(limit);
+        results.count = results.posts.len();
+        Ok(results)
+    }
+
+    /// Search, keeping only posts that pass `filter` and paging (up to `MAX_FILTER_PAGES`)
+    /// until `params.limit` posts qualify or results run out
+    pub async fn search_filtered(
+        &self,
+        params: &SearchParams,
+        filter: &PostFilter,
+    ) -> Result<SearchResults> {
+        self.search_filtered_page(params, filter, &PageCursor::default()).await
+    }
+
+    /// `search_filtered` starting at `cursor`
+    pub async fn search_filtered_page(
+        &self,
+        params: &SearchParams,
+        filter: &PostFilter,
+        cursor: &PageCursor,
+    ) -> Result<SearchResults> {
+        let mut results = self.fetch_search_page(params, cursor).await?;
+        results.posts.retain(|p| filter.matches(p));
+
+        let limit = params.limit as usize;
+        let mut pages = 1;
+        while results.posts.len() < limit && pages < MAX_FILTER_PAGES {
+            let Some(after) = results.after.take() else {
+                break;
+            };
+            let cursor = PageCursor {
+                after: Some(after),
+                before: None,
+            };
+            let page = self.fetch_search_page(params, &cursor).await?;
+            results
+                .posts
+                .extend(page.posts.into_iter().filter(|p| filter.matches(p)));
+            // The next page starts after everything scanned, kept or not
+            results.after = page.after;
+            pages += 1;
+        }
+
+        results.posts.truncate(limit);
+        results.count = results.posts.len();
+        Ok(results)
+    }
+
+    /// Search for subreddits (communities) by name and description
+    pub async fn search_subreddits(&self, query: &str, limit: u32) -> Result<SubredditSearchResults> {
+        let endpoint = format!(
+            "/subreddits/search?q={}&limit={}",
+            urlencoding::encode(query),
+            single_page_limit(limit)
+        );
+        let listing: Listing<Subreddit> = self.get(&endpoint).await?;
+
+        let subreddits: Vec<SubredditSummary> = listing
+            .data
+            .children
+            .into_iter()
+            .map(|t| t.data.into())
+            .collect();
+
+        Ok(SubredditSearchResults {
+            query: query.to_string(),
+            count: subreddits.len(),
+            subreddits,
+        })
+    }
+
+    /// One page of search results, starting at `cursor`
+    async fn fetch_search_page(
+        &self,
+        params: &SearchParams,
+        cursor: &PageCursor,
+    ) -> Result<SearchResults> {
         let mut endpoint = if let Some(ref sub) = params.subreddit {
             format!("/r/{}/search", sub)
         } else {
@@ -112,14 +456,38 @@ impl RedditClient {
             urlencoding::encode(&params.query),
             params.sort,
             params.time,
-            params.limit,
+            params.limit.min(MAX_LISTING_LIMIT),
             params.subreddit.is_some()
         );
 
         endpoint.push_str(&query_params);
 
+        if let Some(ref region) = params.region {
+            endpoint.push_str(&format!("&geo_filter={}", region));
+        }
+        if params.include_facets {
+            endpoint.push_str("&include_facets=on");
+        }
+        if params.sr_detail {
+            endpoint.push_str("&sr_detail=true");
+        }
+        endpoint.push_str(&cursor_query(cursor));
+
         let listing: Listing<Post> = self.get(&endpoint).await?;
 
+        let related_subreddits = if params.include_facets {
+            Some(
+                listing
+                    .data
+                    .facets
+                    .as_ref()
+                    .map(facet_subreddits)
+                    .unwrap_or_default(),
+            )
+        } else {
+            None
+        };
+
         let posts: Vec<PostSummary> = listing
             .data
             .children
@@ -129,13 +497,18 @@ impl RedditClient {
 
         let count = posts.len();
 
-        Ok(SearchResults {
+        let results = SearchResults {
             query: params.query.clone(),
             subreddit: params.subreddit.clone(),
             sort: params.sort.clone(),
             posts,
             count,
-        })
+            after: listing.data.after,
+            before: listing.data.before,
+            related_subreddits,
+            newest_id: None,
+        };
+        Ok(results)
     }
 
     pub async fn get_post(&self, id: &str) -> Result<PostSummary> {
@@ -154,12 +527,97 @@ impl RedditClient {
             .ok_or_else(|| RdtError::RedditApi("Post not found".to_string()))
     }
 
+    /// Fetch several posts with one /by_id request per 100 ids, in the requested order.
+    /// Ids Reddit doesn't return (deleted, bogus) are simply left out.
+    pub async fn get_posts(&self, ids: &[String]) -> Result<Vec<PostSummary>> {
+        let post_ids: Vec<&str> = ids.iter().map(|id| extract_post_id(id)).collect();
+
+        let mut found: HashMap<String, PostSummary> = HashMap::new();
+        for batch in post_ids.chunks(BY_ID_BATCH) {
+            let fullnames: Vec<String> = batch.iter().map(|id| format!("t3_{}", id)).collect();
+            let endpoint = format!("/by_id/{}", fullnames.join(","));
+            let listing: Listing<Post> = self.get(&endpoint).await?;
+            for thing in listing.data.children {
+                found.insert(thing.data.id.clone(), thing.data.into());
+            }
+        }
+
+        Ok(post_ids.iter().filter_map(|id| found.remove(*id)).collect())
+    }
+
+    /// Look up posts (t3_), comments (t1_) and subreddits (t5_) by fullname with one
+    /// /api/info request per 100, in the requested order. Unknown ids are left out.
+    pub async fn get_info(&self, fullnames: &[String]) -> Result<Vec<InfoItem>> {
+        if let Some(bad) = fullnames
+            .iter()
+            .find(|f| !["t1_", "t3_", "t5_"].iter().any(|prefix| f.starts_with(prefix)))
+        {
+            return Err(RdtError::Config(format!(
+                "'{}' isn't a post (t3_), comment (t1_) or subreddit (t5_) fullname",
+                bad
+            )));
+        }
+
+        let mut found: HashMap<String, InfoItem> = HashMap::new();
+        for batch in fullnames.chunks(BY_ID_BATCH) {
+            let endpoint = format!("/api/info?id={}", batch.join(","));
+            let listing: Listing<serde_json::Value> = self.get(&endpoint).await?;
+            for thing in listing.data.children {
+                let Some(name) = thing.data.get("name").and_then(|n| n.as_str()) else {
+                    continue;
+                };
+                let name = name.to_string();
+                if let Some(item) = InfoItem::from_thing(thing) {
+                    found.insert(name, item);
+                }
+            }
+        }
+
+        Ok(fullnames.iter().filter_map(|f| found.remove(f)).collect())
+    }
+
     pub async fn get_comments(
         &self,
         id: &str,
         sort: &str,
         limit: u32,
+        include_replies: bool,
+        min_score: Option<i64>,
     ) -> Result<Vec<CommentSummary>> {
+        let (comments, _) = self
+            .get_comments_capped(id, sort, limit, include_replies, min_score, None)
+            .await?;
+        Ok(comments)
+    }
+
+    /// Like `get_comments`, but stop adding nodes anywhere in the tree once
+    /// `max_comments` have been built. Returns whether anything was left out.
+    pub async fn get_comments_capped(
+        &self,
+        id: &str,
+        sort: &str,
+        limit: u32,
+        include_replies: bool,
+        min_score: Option<i64>,
+        max_comments: Option<usize>,
+    ) -> Result<(Vec<CommentSummary>, bool)> {
+        let thread = self
+            .get_thread(id, sort, limit, include_replies, min_score, max_comments)
+            .await?;
+        Ok((thread.comments, thread.truncated))
+    }
+
+    /// Like `get_comments_capped`, but also returns the post, which Reddit sends
+    /// alongside the comments anyway
+    pub async fn get_thread(
+        &self,
+        id: &str,
+        sort: &str,
+        limit: u32,
+        include_replies: bool,
+        min_score: Option<i64>,
+        max_comments: Option<usize>,
+    ) -> Result<CommentThread> {
         let post_id = extract_post_id(id);
 
         let endpoint = format!("/comments/{}?sort={}&limit={}", post_id, sort, limit);
@@ -167,24 +625,126 @@ impl RedditClient {
         // Reddit returns [post, comments] array
         let response: Vec<Listing<serde_json::Value>> = self.get(&endpoint).await?;
 
+        let post = response
+            .first()
+            .and_then(|listing| listing.data.children.iter().find(|t| t.kind == "t3"))
+            .and_then(|thing| serde_json::from_value::<Post>(thing.data.clone()).ok())
+            .map(PostSummary::from);
+
         let mut comments = Vec::new();
+        let mut budget = NodeBudget::new(max_comments);
 
         if response.len() > 1 {
             for thing in response[1].data.children.iter() {
                 if thing.kind == "t1" {
-                    if let Ok(comment) = serde_json::from_value::<Comment>(thing.data.clone()) {
-                        // Load replies (true) so expand/collapse works
-                        comments.push(CommentSummary::from_comment(comment, true));
+                    if let Some(comment) = parse_comment(&thing.data) {
+                        if !budget.take() {
+                            break;
+                        }
+                        comments.push(CommentSummary::build(comment, include_replies, min_score, &mut budget));
                     }
                 }
             }
         }
 
+        Ok(CommentThread {
+            post,
+            comments,
+            truncated: budget.truncated,
+        })
+    }
+
+    /// Fetch an entire thread as a flat list, expanding "more" stubs.
+    /// Progress is checkpointed to `state_path` after each batch; re-running with the
+    /// same file resumes from there. The file is removed once the thread is complete.
+    pub async fn get_all_comments_resumable(
+        &self,
+        id: &str,
+        sort: &str,
+        state_path: &Path,
+    ) -> Result<Vec<CommentSummary>> {
+        let post_id = extract_post_id(id);
+
+        let mut checkpoint = match CommentCheckpoint::load(state_path, post_id)? {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let endpoint = format!("/comments/{}?sort={}&limit=500", post_id, sort);
+                let response: Vec<Listing<serde_json::Value>> = self.get(&endpoint).await?;
+
+                let mut checkpoint = CommentCheckpoint {
+                    post_id: post_id.to_string(),
+                    ..Default::default()
+                };
+                if let Some(listing) = response.get(1) {
+                    let children: Vec<serde_json::Value> = listing
+                        .data
+                        .children
+                        .iter()
+                        .map(|t| serde_json::json!({ "kind": t.kind, "data": t.data }))
+                        .collect();
+                    flatten_thread(
+                        &children,
+                        &mut checkpoint.fetched,
+                        &mut checkpoint.pending_more,
+                    );
+                }
+                checkpoint.save(state_path)?;
+                checkpoint
+            }
+        };
+
+        let mut seen: HashSet<String> = checkpoint.fetched.iter().map(|c| c.id.clone()).collect();
+
+        while !checkpoint.pending_more.is_empty() {
+            let take = checkpoint.pending_more.len().min(MORE_CHILDREN_BATCH);
+            let batch: Vec<String> = checkpoint.pending_more.drain(..take).collect();
+
+            let endpoint = format!(
+                "/api/morechildren?api_type=json&link_id=t3_{}&sort={}&children={}",
+                post_id,
+                sort,
+                batch.join(",")
+            );
+            let response: serde_json::Value = self.get(&endpoint).await?;
+            let things = response["json"]["data"]["things"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+
+            let mut fetched = Vec::new();
+            flatten_thread(&things, &mut fetched, &mut checkpoint.pending_more);
+            for comment in fetched {
+                if seen.insert(comment.id.clone()) {
+                    checkpoint.fetched.push(comment);
+                }
+            }
+
+            checkpoint.save(state_path)?;
+        }
+
+        // Replies were flattened away, so rebuild reply counts from parent links
+        let mut reply_counts: HashMap<String, usize> = HashMap::new();
+        for comment in &checkpoint.fetched {
+            *reply_counts.entry(comment.parent_id.clone()).or_default() += 1;
+        }
+
+        let comments = checkpoint
+            .fetched
+            .into_iter()
+            .map(|comment| {
+                let reply_count = reply_counts.get(&comment.name).copied().unwrap_or(0);
+                let mut summary = CommentSummary::from_comment(comment, false);
+                summary.reply_count = reply_count;
+                summary
+            })
+            .collect();
+
+        std::fs::remove_file(state_path)?;
         Ok(comments)
     }
 
     pub async fn get_subreddit_info(&self, name: &str) -> Result<SubredditSummary> {
-        let name = name.trim_start_matches("r/");
+        let name = normalize_subreddit(name)?;
         let endpoint = format!("/r/{}/about", name);
 
         #[derive(Deserialize)]
@@ -192,8 +752,52 @@ impl RedditClient {
             data: Subreddit,
         }
 
-        let response: SubredditResponse = self.get(&endpoint).await?;
-        Ok(response.data.into())
+        match self.get::<SubredditResponse>(&endpoint).await {
+            Ok(response) => Ok(response.data.into()),
+            Err(e) => Err(self.subreddit_error(&name, e).await),
+        }
+    }
+
+    /// Turn a 404 for subreddit `name` into `SubredditNotFound`, with similarly named
+    /// subreddits from a subreddit search. Other errors pass through.
+    async fn subreddit_error(&self, name: &str, error: RdtError) -> RdtError {
+        match error {
+            RdtError::RedditApi(ref msg) if msg.starts_with("HTTP 404") => {
+                // Best effort: a failed lookup still reports the missing subreddit
+                // One extra in case the search returns the name itself
+                let search = self.search_subreddits(name, MAX_SUGGESTIONS as u32 + 1).await;
+                let suggestions = match search {
+                    Ok(results) => results
+                        .subreddits
+                        .into_iter()
+                        .map(|s| s.name)
+                        .filter(|n| !n.eq_ignore_ascii_case(name))
+                        .take(MAX_SUGGESTIONS)
+                        .collect(),
+                    Err(_) => Vec::new(),
+                };
+                RdtError::SubredditNotFound {
+                    name: name.to_string(),
+                    suggestions,
+                }
+            }
+            e => e,
+        }
+    }
+
+    /// Daily, hourly and monthly traffic; needs OAuth and (usually) mod access
+    pub async fn get_subreddit_traffic(&self, name: &str) -> Result<TrafficSummary> {
+        self.require_auth()?;
+        let name = normalize_subreddit(name)?;
+        let endpoint = format!("/r/{}/about/traffic", name);
+
+        match self.get::<Traffic>(&endpoint).await {
+            Ok(traffic) => Ok(TrafficSummary::new(name, traffic)),
+            Err(RdtError::RedditApi(msg)) if msg.starts_with("HTTP 403") => Err(RdtError::Forbidden(
+                format!("r/{} traffic is only visible to its moderators", name),
+            )),
+            Err(e) => Err(self.subreddit_error(&name, e).await),
+        }
     }
 
     pub async fn get_subreddit_posts(
@@ -202,11 +806,86 @@ impl RedditClient {
         sort: &str,
         time: &str,
         limit: u32,
+        sr_detail: bool,
     ) -> Result<Vec<PostSummary>> {
-        let name = name.trim_start_matches("r/");
-        let endpoint = format!("/r/{}/{}?t={}&limit={}", name, sort, time, limit);
+        let top = PageCursor::default();
+        let page = self
+            .get_subreddit_posts_from(name, sort, time, limit, sr_detail, &top)
+            .await?;
+        Ok(page.posts)
+    }
 
-        let listing: Listing<Post> = self.get(&endpoint).await?;
+    /// Up to `limit` posts of a subreddit listing starting at `cursor`, with the
+    /// cursors for the neighbouring pages
+    pub async fn get_subreddit_posts_from(
+        &self,
+        name: &str,
+        sort: &str,
+        time: &str,
+        limit: u32,
+        sr_detail: bool,
+        cursor: &PageCursor,
+    ) -> Result<PostPage> {
+        // Only `after` can be followed forwards; a `before` request is a single page
+        if cursor.before.is_some() {
+            return self
+                .get_subreddit_posts_page(name, sort, time, limit, sr_detail, cursor)
+                .await;
+        }
+
+        // Limits over Reddit's per-request cap are met by following `after`
+        let mut result = PostPage::default();
+        let mut cursor = cursor.clone();
+        loop {
+            let remaining = limit.saturating_sub(result.posts.len() as u32);
+            let page = self
+                .get_subreddit_posts_page(
+                    name,
+                    sort,
+                    time,
+                    remaining.min(MAX_LISTING_LIMIT),
+                    sr_detail,
+                    &cursor,
+                )
+                .await?;
+            if result.posts.is_empty() {
+                result.before = page.before;
+            }
+            result.posts.extend(page.posts);
+            result.after = page.after;
+            if result.after.is_none() || result.posts.len() as u32 >= limit {
+                break;
+            }
+            cursor.after = result.after.clone();
+        }
+        result.posts.truncate(limit as usize);
+        result.count = result.posts.len();
+        Ok(result)
+    }
+
+    /// One page of a subreddit listing, starting at `cursor`; its `after` is None at
+    /// the end of the listing. `limit` is capped at Reddit's 100 per request.
+    pub async fn get_subreddit_posts_page(
+        &self,
+        name: &str,
+        sort: &str,
+        time: &str,
+        limit: u32,
+        sr_detail: bool,
+        cursor: &PageCursor,
+    ) -> Result<PostPage> {
+        let name = normalize_listing_subreddit(name)?;
+        let limit = single_page_limit(limit);
+        let mut endpoint = format!("/r/{}/{}?t={}&limit={}", name, sort, time, limit);
+        if sr_detail {
+            endpoint.push_str("&sr_detail=true");
+        }
+        endpoint.push_str(&cursor_query(cursor));
+
+        let listing: Listing<Post> = match self.get(&endpoint).await {
+            Ok(listing) => listing,
+            Err(e) => return Err(self.subreddit_error(&name, e).await),
+        };
 
         let posts = listing
             .data
@@ -215,7 +894,60 @@ impl RedditClient {
             .map(|t| t.data.into())
             .collect();
 
-        Ok(posts)
+        Ok(PostPage::new(posts, listing.data.after, listing.data.before))
+    }
+
+    /// Posts from the "new" listing that are newer than `since_id`, paging (up to
+    /// `MAX_FILTER_PAGES`) until that post is reached, `limit` posts are collected,
+    /// or results run out. Newest first.
+    pub async fn get_subreddit_posts_since(
+        &self,
+        name: &str,
+        since_id: &str,
+        limit: u32,
+    ) -> Result<Vec<PostSummary>> {
+        Ok(self.get_new_posts_since(name, since_id, limit).await?.posts)
+    }
+
+    /// `get_subreddit_posts_since`, also saying whether `since_id` was reached
+    pub async fn get_new_posts_since(
+        &self,
+        name: &str,
+        since_id: &str,
+        limit: u32,
+    ) -> Result<NewPosts> {
+        let name = normalize_listing_subreddit(name)?;
+        let since_id = extract_post_id(since_id);
+        let limit = limit as usize;
+
+        let mut posts = Vec::new();
+        let mut after: Option<String> = None;
+        for _ in 0..MAX_FILTER_PAGES {
+            let mut endpoint = format!("/r/{}/new?limit=100", name);
+            if let Some(ref cursor) = after {
+                endpoint.push_str(&format!("&after={}", urlencoding::encode(cursor)));
+            }
+            let listing: Listing<Post> = match self.get(&endpoint).await {
+                Ok(listing) => listing,
+                Err(e) => return Err(self.subreddit_error(&name, e).await),
+            };
+            after = listing.data.after;
+
+            for thing in listing.data.children {
+                if thing.data.id == since_id {
+                    return Ok(NewPosts { posts, reached_since: true });
+                }
+                if posts.len() >= limit {
+                    return Ok(NewPosts { posts, reached_since: false });
+                }
+                posts.push(thing.data.into());
+            }
+            if after.is_none() {
+                break;
+            }
+        }
+
+        Ok(NewPosts { posts, reached_since: false })
     }
 
     pub async fn get_user_info(&self, username: &str) -> Result<UserSummary> {
@@ -231,14 +963,72 @@ impl RedditClient {
         Ok(response.data.into())
     }
 
+    /// Subreddits the user moderates; empty when they moderate none or hide the list
+    pub async fn get_user_moderated(&self, username: &str) -> Result<Vec<ModeratedSummary>> {
+        let username = username.trim_start_matches("u/");
+        let endpoint = format!("/user/{}/moderated_subreddits", username);
+
+        // Reddit answers `{}` (no data) for users who moderate nothing
+        #[derive(Deserialize)]
+        struct ModeratedResponse {
+            #[serde(default)]
+            data: Vec<ModeratedSubreddit>,
+        }
+
+        match self.get::<ModeratedResponse>(&endpoint).await {
+            Ok(response) => Ok(response.data.into_iter().map(Into::into).collect()),
+            Err(RdtError::RedditApi(msg)) if msg.starts_with("HTTP 403") => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The user's trophies; empty for accounts that have none
+    pub async fn get_user_trophies(&self, username: &str) -> Result<Vec<Trophy>> {
+        let username = username.trim_start_matches("u/");
+        let endpoint = format!("/user/{}/trophies", username);
+
+        #[derive(Deserialize)]
+        struct TrophyList {
+            #[serde(default)]
+            trophies: Vec<Thing<Trophy>>,
+        }
+        #[derive(Deserialize)]
+        struct TrophyResponse {
+            data: TrophyList,
+        }
+
+        let response: TrophyResponse = self.get(&endpoint).await?;
+        Ok(response.data.trophies.into_iter().map(|t| t.data).collect())
+    }
+
+    /// `time` only applies to the top and controversial sorts; it's ignored otherwise
     pub async fn get_user_posts(
         &self,
         username: &str,
         sort: &str,
+        time: Option<&str>,
         limit: u32,
     ) -> Result<Vec<PostSummary>> {
+        let top = PageCursor::default();
+        Ok(self.get_user_posts_page(username, sort, time, limit, &top).await?.posts)
+    }
+
+    /// One page of a user's posts starting at `cursor`
+    pub async fn get_user_posts_page(
+        &self,
+        username: &str,
+        sort: &str,
+        time: Option<&str>,
+        limit: u32,
+        cursor: &PageCursor,
+    ) -> Result<PostPage> {
         let username = username.trim_start_matches("u/");
-        let endpoint = format!("/user/{}/submitted?sort={}&limit={}", username, sort, limit);
+        let limit = single_page_limit(limit);
+        let mut endpoint = format!("/user/{}/submitted?sort={}&limit={}", username, sort, limit);
+        if let (Some(time), "top" | "controversial") = (time, sort) {
+            endpoint.push_str(&format!("&t={}", time));
+        }
+        endpoint.push_str(&cursor_query(cursor));
 
         let listing: Listing<Post> = self.get(&endpoint).await?;
 
@@ -249,12 +1039,367 @@ impl RedditClient {
             .map(|t| t.data.into())
             .collect();
 
-        Ok(posts)
+        Ok(PostPage::new(posts, listing.data.after, listing.data.before))
+    }
+
+    /// Create a self post (`text`) or link post (`url`); exactly one should be given
+    pub async fn submit_post(
+        &self,
+        subreddit: &str,
+        title: &str,
+        text: Option<&str>,
+        url: Option<&str>,
+    ) -> Result<SubmittedPost> {
+        self.require_auth()?;
+        let subreddit = normalize_subreddit(subreddit)?;
+        let mut form = vec![("sr", subreddit.as_str()), ("title", title)];
+        match (text, url) {
+            (_, Some(url)) => form.extend([("kind", "link"), ("url", url)]),
+            (text, None) => form.extend([("kind", "self"), ("text", text.unwrap_or_default())]),
+        }
+
+        let response: ActionResponse<SubmittedPost> = self.post_form("/api/submit", &form).await?;
+        response.into_result()?.ok_or_else(|| {
+            RdtError::RedditApi("Reddit accepted the post but didn't return it".to_string())
+        })
+    }
+
+    /// Vote on a post (`t3_`) or comment (`t1_`) fullname: `dir` is 1 (up), -1 (down)
+    /// or 0 (clear)
+    pub async fn vote(&self, fullname: &str, dir: i8) -> Result<()> {
+        self.require_auth()?;
+        let dir = dir.to_string();
+        self.post_form::<serde_json::Value>("/api/vote", &[("id", fullname), ("dir", &dir)])
+            .await?;
+        Ok(())
+    }
+
+    /// Reply to a post (`t3_`) or comment (`t1_`) fullname and return the new comment
+    pub async fn reply(&self, parent: &str, text: &str) -> Result<CommentSummary> {
+        self.require_auth()?;
+
+        #[derive(Deserialize)]
+        struct ReplyData {
+            things: Vec<Thing<serde_json::Value>>,
+        }
+
+        let form = [("thing_id", parent), ("text", text)];
+        let response: ActionResponse<ReplyData> = self.post_form("/api/comment", &form).await?;
+        // Replies that can never succeed get a plain explanation instead of Reddit's code
+        let reason = match response.error_code() {
+            Some("THREAD_LOCKED") => Some("the thread is locked"),
+            Some("TOO_OLD") => Some("the post is archived"),
+            Some("DELETED_COMMENT" | "DELETED_LINK") => Some("the parent was deleted"),
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            return Err(RdtError::Forbidden(format!("can't reply to {}: {}", parent, reason)));
+        }
+
+        response
+            .into_result()?
+            .and_then(|data| data.things.into_iter().next())
+            .and_then(|thing| parse_comment(&thing.data))
+            .map(Into::into)
+            .ok_or_else(|| {
+                RdtError::RedditApi("Reddit accepted the reply but didn't return it".to_string())
+            })
+    }
+
+    pub async fn get_user_comments(
+        &self,
+        username: &str,
+        sort: &str,
+        limit: u32,
+    ) -> Result<Vec<CommentSummary>> {
+        let username = username.trim_start_matches("u/");
+        let limit = single_page_limit(limit);
+        let endpoint = format!("/user/{}/comments?sort={}&limit={}", username, sort, limit);
+
+        let listing: Listing<Comment> = self.get(&endpoint).await?;
+
+        let comments = listing
+            .data
+            .children
+            .into_iter()
+            .map(|t| t.data.into())
+            .collect();
+
+        Ok(comments)
+    }
+}
+
+/// Body of an `api_type=json` action response: `{"json": {"errors": [...], "data": ...}}`
+#[derive(Deserialize)]
+struct ActionResponse<T> {
+    json: ActionBody<T>,
+}
+
+#[derive(Deserialize)]
+struct ActionBody<T> {
+    /// `[code, message, field]` triples, e.g. `["NO_TEXT", "we need something here", "title"]`
+    #[serde(default)]
+    errors: Vec<Vec<serde_json::Value>>,
+    data: Option<T>,
+}
+
+impl<T> ActionResponse<T> {
+    /// Code of the first error, e.g. "THREAD_LOCKED"
+    fn error_code(&self) -> Option<&str> {
+        self.json.errors.first()?.first()?.as_str()
+    }
+
+    /// The data, or Reddit's errors as one `RedditApi` error
+    fn into_result(self) -> Result<Option<T>> {
+        if self.json.errors.is_empty() {
+            return Ok(self.json.data);
+        }
+        let errors: Vec<String> = self
+            .json
+            .errors
+            .iter()
+            .map(|error| {
+                let part = |i: usize| error.get(i).and_then(|v| v.as_str()).unwrap_or_default();
+                match part(2) {
+                    "" => format!("{}: {}", part(0), part(1)),
+                    field => format!("{}: {} ({})", part(0), part(1), field),
+                }
+            })
+            .collect();
+        Err(RdtError::RedditApi(errors.join("; ")))
+    }
+}
+
+/// `limit` for a listing fetched in one request: capped at Reddit's maximum
+fn single_page_limit(limit: u32) -> u32 {
+    limit.min(MAX_LISTING_LIMIT)
+}
+
+/// Normalize a subreddit name: strip `r/` or `/r/`, lowercase, and validate
+/// against Reddit's naming rule (2-21 chars of `[A-Za-z0-9_]`)
+pub fn normalize_subreddit(input: &str) -> Result<String> {
+    let trimmed = input.trim().trim_end_matches('/');
+    let name = trimmed
+        .strip_prefix("/r/")
+        .or_else(|| trimmed.strip_prefix("r/"))
+        .unwrap_or(trimmed);
+
+    let valid = (2..=21).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(RdtError::Config(format!("Invalid subreddit name: {}", input)));
+    }
+
+    Ok(name.to_lowercase())
+}
+
+/// `normalize_subreddit` for listing and search endpoints, which also take
+/// multireddits (`rust+golang`) and exclusions (`all-politics-news`); each name is
+/// checked on its own
+pub fn normalize_listing_subreddit(input: &str) -> Result<String> {
+    let trimmed = input.trim().trim_end_matches('/');
+    let names = trimmed
+        .strip_prefix("/r/")
+        .or_else(|| trimmed.strip_prefix("r/"))
+        .unwrap_or(trimmed);
+
+    let mut parts = Vec::new();
+    for part in names.split('+') {
+        let names: Vec<String> = part
+            .split('-')
+            .map(normalize_subreddit)
+            .collect::<Result<_>>()
+            .map_err(|_| RdtError::Config(format!("Invalid subreddit name: {}", input)))?;
+        parts.push(names.join("-"));
+    }
+    Ok(parts.join("+"))
+}
+
+/// Walk `t1`/`more` things depth-first, collecting comments (with `replies` cleared)
+/// and the ids of any unexpanded "more" stubs
+fn flatten_thread(
+    children: &[serde_json::Value],
+    comments: &mut Vec<Comment>,
+    pending_more: &mut Vec<String>,
+) {
+    for child in children {
+        let data = &child["data"];
+        match child["kind"].as_str() {
+            Some("t1") => {
+                if let Some(mut comment) = parse_comment(data) {
+                    let replies = std::mem::take(&mut comment.replies);
+                    comments.push(comment);
+                    if let Some(nested) = replies["data"]["children"].as_array() {
+                        flatten_thread(nested, comments, pending_more);
+                    }
+                }
+            }
+            Some("more") => {
+                // "continue this thread" stubs have no children ids and are skipped
+                if let Some(ids) = data["children"].as_array() {
+                    pending_more.extend(ids.iter().filter_map(|id| id.as_str().map(String::from)));
+                }
+            }
+            _ => {}
+        }
     }
 }
 
+/// Pull subreddit names out of a search response's `facets` section
+fn facet_subreddits(facets: &serde_json::Value) -> Vec<String> {
+    facets
+        .get("subreddits")
+        .and_then(|v| v.as_array())
+        .map(|subs| {
+            subs.iter()
+                .filter_map(|sub| {
+                    sub.get("name")
+                        .or_else(|| sub.get("display_name"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Turn a Reddit response into `T`, mapping rate limits, error statuses and HTML
+/// block pages to errors
+async fn read_response<T: for<'de> Deserialize<'de>>(response: reqwest::Response) -> Result<T> {
+    if response.status() == 429 {
+        return Err(RdtError::RateLimited {
+            reset_secs: rate_limit_reset_secs(response.headers()),
+        });
+    }
+
+    // The public API sometimes answers with an HTML page (login wall, block page)
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/html"));
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        // A JSON 403 is a real answer (private subreddit); an HTML one is the bot wall
+        if status == 403 && (is_html || is_block_page(&body)) {
+            return Err(RdtError::RedditApi(BLOCKED_MESSAGE.to_string()));
+        }
+        return Err(RdtError::RedditApi(format!(
+            "HTTP {}: {}",
+            status, body
+        )));
+    }
+
+    // Get the raw text first to debug deserialization issues
+    let text = response.text().await?;
+
+    if is_html {
+        if is_block_page(&text) {
+            return Err(RdtError::RedditApi(BLOCKED_MESSAGE.to_string()));
+        }
+        return Err(RdtError::RedditApi(
+            "got HTML, are you rate-limited or is this endpoint OAuth-only?".to_string(),
+        ));
+    }
+
+    let data: T = serde_json::from_str(&text).map_err(|e| {
+        RdtError::RedditApi(format!(
+            "JSON parse error: {} (first 500 chars: {})",
+            e,
+            &text[..text.len().min(500)]
+        ))
+    })?;
+
+    Ok(data)
+}
+
+/// Seconds to wait after a 429, from `Retry-After` or else Reddit's `x-ratelimit-reset`
+/// (rounded up, since the latter can be fractional)
+pub fn rate_limit_reset_secs(headers: &HeaderMap) -> Option<u64> {
+    [reqwest::header::RETRY_AFTER.as_str(), "x-ratelimit-reset"]
+        .iter()
+        .find_map(|name| {
+            let value = headers.get(*name)?.to_str().ok()?.trim().parse::<f64>().ok()?;
+            (value >= 0.0).then(|| value.ceil() as u64)
+        })
+}
+
+/// Validate `http.extra_headers` from config into a header map
+fn extra_headers(config: &Config) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.http.extra_headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            RdtError::Config(format!("Invalid header name in http.extra_headers: '{}'", name))
+        })?;
+        if header_name == USER_AGENT || header_name == AUTHORIZATION {
+            return Err(RdtError::Config(format!(
+                "http.extra_headers can't set '{}' (use reddit.user_agent / rdt auth login)",
+                name
+            )));
+        }
+        let header_value = HeaderValue::from_str(value).map_err(|_| {
+            RdtError::Config(format!("Invalid value for header '{}' in http.extra_headers", name))
+        })?;
+        headers.insert(header_name, header_value);
+    }
+    Ok(headers)
+}
+
+fn resolve_base(
+    override_url: Option<&str>,
+    env_var: &str,
+    configured: Option<&str>,
+    default: &str,
+) -> String {
+    let env_url = std::env::var(env_var).ok().filter(|v| !v.is_empty());
+    override_url
+        .or(env_url.as_deref())
+        .or(configured)
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Whether a response body is one of Reddit's anti-bot challenge/block pages
+fn is_block_page(body: &str) -> bool {
+    let body = body.to_lowercase();
+    BLOCK_PAGE_MARKERS.iter().any(|marker| body.contains(marker))
+}
+
+/// `&after=...`/`&before=...` for a listing URL; empty for the default cursor
+fn cursor_query(cursor: &PageCursor) -> String {
+    let mut query = String::new();
+    if let Some(ref after) = cursor.after {
+        query.push_str(&format!("&after={}", urlencoding::encode(after)));
+    }
+    if let Some(ref before) = cursor.before {
+        query.push_str(&format!("&before={}", urlencoding::encode(before)));
+    }
+    query
+}
+
+/// Cursor for a post listing from `--after`/`--before`, which accept a post id,
+/// fullname or URL
+pub fn post_cursor(after: Option<&str>, before: Option<&str>) -> PageCursor {
+    let fullname = |id: &str| format!("t3_{}", extract_post_id(id));
+    PageCursor {
+        after: after.map(fullname),
+        before: before.map(fullname),
+    }
+}
+
+/// Fullname for a post (id, URL or `t3_...`) or comment (`t1_...`) to act on
+pub fn thing_fullname(input: &str) -> String {
+    if input.starts_with("t1_") {
+        return input.to_string();
+    }
+    format!("t3_{}", extract_post_id(input))
+}
+
 /// Extract post ID from various formats
-fn extract_post_id(input: &str) -> &str {
+pub fn extract_post_id(input: &str) -> &str {
     // Handle full URLs like https://reddit.com/r/rust/comments/abc123/title
     if input.contains("/comments/") {
         if let Some(idx) = input.find("/comments/") {
@@ -271,3 +1416,853 @@ fn extract_post_id(input: &str) -> &str {
     // Assume it's already just the ID
     input
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // Public (non-OAuth) client pointed at the mock server
+    fn mock_client(server: &MockServer) -> RedditClient {
+        RedditClient::from_config(Config::default(), Some(server.uri())).unwrap()
+    }
+
+    fn about_rust() -> serde_json::Value {
+        json!({
+            "data": {
+                "id": "2qh1i",
+                "name": "t5_2qh1i",
+                "display_name": "rust",
+                "display_name_prefixed": "r/rust",
+                "title": "Rust",
+                "public_description": "",
+                "subscribers": 1,
+                "active_user_count": null,
+                "created_utc": 1_200_000_000.0,
+                "over18": false,
+                "url": "/r/rust/",
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_public_requests_use_json_suffix() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/r/rust/about.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(about_rust()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let info = mock_client(&server).get_subreddit_info("r/rust").await.unwrap();
+        assert_eq!(info.name, "rust");
+    }
+
+    #[tokio::test]
+    async fn test_configured_public_base_url() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/r/rust/about.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(about_rust()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = Config::default();
+        config.reddit.public_base_url = Some(format!("{}/", server.uri()));
+        let client = RedditClient::from_config(config, None).unwrap();
+        assert!(client.get_subreddit_info("rust").await.is_ok());
+        // Credentials still only go to reddit.com
+        assert_eq!(client.token_url, REDDIT_TOKEN_URL);
+    }
+
+    #[tokio::test]
+    async fn test_extra_headers_sent() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("x-trace-id", "abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(about_rust()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = Config::default();
+        config.http.extra_headers.insert("X-Trace-Id".into(), "abc".into());
+        let client = RedditClient::from_config(config, Some(server.uri())).unwrap();
+        assert!(client.get_subreddit_info("rust").await.is_ok());
+
+        let mut config = Config::default();
+        config.http.extra_headers.insert("User-Agent".into(), "spoof".into());
+        assert!(RedditClient::from_config(config, None).is_err());
+    }
+
+    fn search_page(posts: &[(&str, u64)], after: Option<&str>) -> serde_json::Value {
+        let children: Vec<_> = posts
+            .iter()
+            .map(|(id, num_comments)| {
+                json!({ "kind": "t3", "data": { "id": id, "num_comments": num_comments } })
+            })
+            .collect();
+        json!({ "kind": "Listing", "data": { "after": after, "before": null, "children": children } })
+    }
+
+    #[tokio::test]
+    async fn test_thread_includes_post() {
+        let server = MockServer::start().await;
+        let comment = json!({
+            "id": "c1", "name": "t1_c1", "author": "a", "body": "hi", "score": 1,
+            "created_utc": 0.0, "parent_id": "t3_abc", "link_id": "t3_abc", "replies": "",
+        });
+        Mock::given(path("/comments/abc.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "kind": "Listing", "data": { "after": null, "before": null, "children": [
+                    { "kind": "t3", "data": { "id": "abc", "selftext": "the body" } },
+                ] } },
+                { "kind": "Listing", "data": { "after": null, "before": null, "children": [
+                    { "kind": "t1", "data": comment },
+                ] } },
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let thread = mock_client(&server)
+            .get_thread("abc", "best", 10, false, None, None)
+            .await
+            .unwrap();
+        let post = thread.post.unwrap();
+        assert_eq!(post.id, "abc");
+        assert_eq!(post.selftext.as_deref(), Some("the body"));
+        assert_eq!(thread.comments.len(), 1);
+        assert!(!thread.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_info_mixed_kinds_in_order() {
+        let server = MockServer::start().await;
+        let mut sub = about_rust()["data"].clone();
+        sub["name"] = json!("t5_2qh1i");
+        Mock::given(path("/api/info.json"))
+            .and(query_param("id", "t5_2qh1i,t1_c1,t3_gone,t3_abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "kind": "Listing",
+                "data": { "after": null, "before": null, "children": [
+                    { "kind": "t3", "data": { "id": "abc", "name": "t3_abc" } },
+                    { "kind": "t1", "data": {
+                        "id": "c1", "name": "t1_c1", "author": "a", "body": "hi", "score": 1,
+                        "created_utc": 0.0, "parent_id": "t3_abc", "link_id": "t3_abc",
+                    } },
+                    { "kind": "t5", "data": sub },
+                ] },
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let ids: Vec<String> = ["t5_2qh1i", "t1_c1", "t3_gone", "t3_abc"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let items = client.get_info(&ids).await.unwrap();
+        let kinds: Vec<String> = items
+            .iter()
+            .map(|item| serde_json::to_value(item).unwrap()["kind"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(kinds, ["subreddit", "comment", "post"]);
+
+        assert!(client.get_info(&["abc123".to_string()]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_refreshed_once() {
+        let server = MockServer::start().await;
+        Mock::given(path("/api/v1/me"))
+            .and(header("authorization", "Bearer new-tok"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "1w72", "name": "spez", "link_karma": 1, "comment_karma": 1,
+                "created_utc": 1_118_030_400.0,
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/api/v1/me"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(json!({ "error": 401 })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "new-tok",
+                "refresh_token": "rotated",
+                "token_type": "bearer",
+                "expires_in": 86400,
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("rdt-refresh-{}", std::process::id()));
+        let mut config = Config::in_dir(dir.clone());
+        config.reddit.client_id = Some("cid".to_string());
+        config.reddit.access_token = Some("old-tok".to_string());
+        config.reddit.refresh_token = Some("refresh".to_string());
+        let client = RedditClient::from_config(config, Some(server.uri())).unwrap();
+
+        assert_eq!(client.get_me().await.unwrap().name, "spez");
+        let requests = server.received_requests().await.unwrap();
+        let form = String::from_utf8_lossy(&requests[1].body).to_string();
+        assert_eq!(form, "grant_type=refresh_token&refresh_token=refresh");
+
+        let saved = std::fs::read_to_string(dir.join("config.toml")).unwrap();
+        assert!(saved.contains("access_token = \"new-tok\""), "{}", saved);
+        assert!(saved.contains("refresh_token = \"rotated\""), "{}", saved);
+
+        // The next refresh uses the rotated token, not the one the client started with
+        client.refresh_access_token().await.unwrap();
+        let requests = server.received_requests().await.unwrap();
+        let form = String::from_utf8_lossy(&requests.last().unwrap().body).to_string();
+        assert_eq!(form, "grant_type=refresh_token&refresh_token=rotated");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_submit_post() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/submit"))
+            .and(header("authorization", "Bearer tok"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "json": {
+                "errors": [],
+                "data": {
+                    "id": "abc123",
+                    "name": "t3_abc123",
+                    "url": "https://www.reddit.com/r/test/comments/abc123/hello/",
+                },
+            } })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/submit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "json": {
+                "errors": [["SUBREDDIT_NOEXIST", "that subreddit doesn't exist", "sr"]],
+            } })))
+            .mount(&server)
+            .await;
+
+        let mut config = Config::default();
+        config.reddit.access_token = Some("tok".to_string());
+        let client = RedditClient::from_config(config, Some(server.uri())).unwrap();
+
+        let post = client.submit_post("r/test", "Hello", Some("body"), None).await.unwrap();
+        assert_eq!(post.fullname, "t3_abc123");
+        let requests = server.received_requests().await.unwrap();
+        let form = String::from_utf8_lossy(&requests[0].body).to_string();
+        assert_eq!(form, "sr=test&title=Hello&kind=self&text=body&api_type=json");
+
+        let err = client.submit_post("nope", "Hi", None, Some("https://x.y")).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Reddit API error: SUBREDDIT_NOEXIST: that subreddit doesn't exist (sr)"
+        );
+
+        let err = mock_client(&server).submit_post("test", "Hi", Some("x"), None).await;
+        assert!(matches!(err, Err(RdtError::NotAuthenticated)));
+    }
+
+    #[tokio::test]
+    async fn test_vote_sends_fullname_and_dir() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/vote"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .mount(&server)
+            .await;
+
+        let mut config = Config::default();
+        config.reddit.access_token = Some("tok".to_string());
+        let client = RedditClient::from_config(config, Some(server.uri())).unwrap();
+
+        let post = thing_fullname("https://reddit.com/r/rust/comments/abc123/title/");
+        client.vote(&post, 1).await.unwrap();
+        client.vote(&thing_fullname("t1_def456"), -1).await.unwrap();
+        let requests = server.received_requests().await.unwrap();
+        let forms: Vec<String> = requests
+            .iter()
+            .map(|r| String::from_utf8_lossy(&r.body).to_string())
+            .collect();
+        assert_eq!(forms[0], "id=t3_abc123&dir=1&api_type=json");
+        assert_eq!(forms[1], "id=t1_def456&dir=-1&api_type=json");
+    }
+
+    #[tokio::test]
+    async fn test_reply_returns_comment_or_explains_lock() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/comment"))
+            .and(body_string_contains("thing_id=t1_c1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "json": {
+                "errors": [],
+                "data": { "things": [{ "kind": "t1", "data": {
+                    "id": "c2", "name": "t1_c2", "author": "me", "body": "Thanks!", "score": 1,
+                    "created_utc": 0.0, "parent_id": "t1_c1", "link_id": "t3_abc",
+                } }] },
+            } })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/comment"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "json": {
+                "errors": [["THREAD_LOCKED", "Comments are locked.", "parent"]],
+            } })))
+            .mount(&server)
+            .await;
+
+        let mut config = Config::default();
+        config.reddit.access_token = Some("tok".to_string());
+        let client = RedditClient::from_config(config, Some(server.uri())).unwrap();
+
+        let comment = client.reply("t1_c1", "Thanks!").await.unwrap();
+        assert_eq!((comment.id.as_str(), comment.body.as_str()), ("c2", "Thanks!"));
+
+        let err = client.reply("t3_abc", "Hi").await.unwrap_err();
+        assert!(matches!(err, RdtError::Forbidden(_)), "{:?}", err);
+        assert!(err.to_string().contains("locked"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_headers_are_kept() {
+        let server = MockServer::start().await;
+        Mock::given(path("/r/all/new.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-ratelimit-remaining", "598.0")
+                    .insert_header("x-ratelimit-reset", "231")
+                    .set_body_json(search_page(&[("a", 0)], None)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        assert_eq!(client.rate_limit(), None);
+        client.ping().await.unwrap();
+        assert_eq!(
+            client.rate_limit(),
+            Some(RateLimit {
+                remaining: 598,
+                reset_secs: 231
+            })
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("3"));
+        assert_eq!(RateLimit::from_headers(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_ping_uses_one_small_request() {
+        let server = MockServer::start().await;
+        Mock::given(path("/r/all/new.json"))
+            .and(query_param("limit", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(search_page(&[("a", 0)], None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        client.ping().await.unwrap();
+        assert!(!client.is_authenticated());
+        assert_eq!(client.base_url(), server.uri());
+    }
+
+    #[tokio::test]
+    async fn test_listing_accepts_multireddits_and_exclusions() {
+        assert_eq!(normalize_listing_subreddit("r/Rust+golang").unwrap(), "rust+golang");
+        assert_eq!(normalize_listing_subreddit("all-politics-news").unwrap(), "all-politics-news");
+        assert!(normalize_listing_subreddit("rust+").is_err());
+        assert!(normalize_listing_subreddit("all-x").is_err());
+        // Single-name callers stay strict
+        assert!(normalize_subreddit("rust+golang").is_err());
+
+        let server = MockServer::start().await;
+        Mock::given(path("/r/rust+golang/hot.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(search_page(&[("a", 0)], None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/r/all-politics/hot.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(search_page(&[("b", 0)], None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        for name in ["rust+golang", "r/all-politics"] {
+            let posts = client.get_subreddit_posts(name, "hot", "day", 25, false).await.unwrap();
+            assert_eq!(posts.len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subreddit_limit_over_cap_pages() {
+        let server = MockServer::start().await;
+        Mock::given(path("/r/rust/hot.json"))
+            .and(query_param("after", "t3_b"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(search_page(&[("c", 0), ("d", 0)], None)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/r/rust/hot.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(search_page(&[("a", 0), ("b", 0)], Some("t3_b"))),
+            )
+            .mount(&server)
+            .await;
+
+        let page = mock_client(&server)
+            .get_subreddit_posts_from("rust", "hot", "day", 150, false, &PageCursor::default())
+            .await
+            .unwrap();
+        assert_eq!(page.count, 4);
+        assert_eq!(page.after, None);
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|r| r.url.query_pairs().any(|(k, v)| k == "limit" && v == "100")));
+    }
+
+    #[tokio::test]
+    async fn test_search_limit_over_cap_pages() {
+        let server = MockServer::start().await;
+        Mock::given(path("/search.json"))
+            .and(query_param("after", "t3_b"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(search_page(&[("c", 0), ("d", 0)], None)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/search.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(search_page(&[("a", 0), ("b", 0)], Some("t3_b"))),
+            )
+            .mount(&server)
+            .await;
+
+        let params = SearchParams {
+            query: "rust".to_string(),
+            limit: 150,
+            ..Default::default()
+        };
+        let results = mock_client(&server).search(&params).await.unwrap();
+        assert_eq!(results.count, 4);
+        assert_eq!(results.after, None);
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|r| r.url.query_pairs().any(|(k, v)| k == "limit" && v == "100")));
+    }
+
+    #[tokio::test]
+    async fn test_search_filtered_pages_until_limit() {
+        let server = MockServer::start().await;
+        Mock::given(path("/search.json"))
+            .and(query_param("after", "t3_b"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(search_page(&[("c", 50), ("d", 9)], None)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/search.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(search_page(&[("a", 12), ("b", 0)], Some("t3_b"))),
+            )
+            .mount(&server)
+            .await;
+
+        let params = SearchParams {
+            query: "rust".to_string(),
+            limit: 2,
+            ..Default::default()
+        };
+        let filter = PostFilter {
+            min_comments: Some(10),
+        };
+        let results = mock_client(&server)
+            .search_filtered(&params, &filter)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = results.posts.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c"]);
+        assert_eq!(results.count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_posts_since_stops_at_id() {
+        let server = MockServer::start().await;
+        Mock::given(path("/r/rust/new.json"))
+            .and(query_param("after", "t3_b"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(search_page(&[("c", 0), ("d", 0)], None)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/r/rust/new.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(search_page(&[("a", 0), ("b", 0)], Some("t3_b"))),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let ids = |posts: Vec<PostSummary>| posts.into_iter().map(|p| p.id).collect::<Vec<_>>();
+        let posts = client.get_subreddit_posts_since("rust", "t3_d", 25).await.unwrap();
+        assert_eq!(ids(posts), vec!["a", "b", "c"]);
+        let posts = client.get_subreddit_posts_since("rust", "a", 25).await.unwrap();
+        assert!(posts.is_empty());
+        let posts = client.get_subreddit_posts_since("rust", "d", 1).await.unwrap();
+        assert_eq!(ids(posts), vec!["a"]);
+
+        // Stopping at the limit, or never seeing the id, is flagged as incomplete
+        assert!(client.get_new_posts_since("rust", "b", 25).await.unwrap().reached_since);
+        let cut_off = client.get_new_posts_since("rust", "d", 1).await.unwrap();
+        assert!(!cut_off.reached_since);
+        let missing = client.get_new_posts_since("rust", "gone", 25).await.unwrap();
+        assert_eq!(ids(missing.posts), vec!["a", "b", "c", "d"]);
+        assert!(!missing.reached_since);
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_keeps_requested_order() {
+        let server = MockServer::start().await;
+        Mock::given(path("/by_id/t3_c,t3_a,t3_gone,t3_b.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(search_page(&[("a", 0), ("b", 0), ("c", 0)], None)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let ids: Vec<String> = ["c", "t3_a", "gone", "https://reddit.com/r/x/comments/b/title"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let posts = mock_client(&server).get_posts(&ids).await.unwrap();
+        let ids: Vec<&str> = posts.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_user_posts_time_only_for_top() {
+        let server = MockServer::start().await;
+        Mock::given(path("/user/spez/submitted.json"))
+            .and(query_param("sort", "top"))
+            .and(query_param("t", "year"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(search_page(&[("a", 0)], None)))
+            .mount(&server)
+            .await;
+        Mock::given(path("/user/spez/submitted.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(search_page(&[], None)))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let posts = client.get_user_posts("spez", "top", Some("year"), 25).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests[0].url.query(), Some("sort=top&limit=25&t=year"));
+
+        client.get_user_posts("spez", "new", Some("year"), 25).await.unwrap();
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests[1].url.query(), Some("sort=new&limit=25"));
+    }
+
+    #[tokio::test]
+    async fn test_cursor_flags_reach_listing_and_come_back() {
+        let server = MockServer::start().await;
+        Mock::given(path("/user/spez/submitted.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(search_page(&[("b", 0), ("c", 0)], Some("t3_c"))),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/search.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(search_page(&[("z", 0)], None)))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let cursor = post_cursor(Some("https://reddit.com/r/x/comments/abc/title"), None);
+        let page = client.get_user_posts_page("spez", "new", None, 2, &cursor).await.unwrap();
+        assert_eq!(page.after.as_deref(), Some("t3_c"));
+
+        let params = SearchParams {
+            query: "rust".to_string(),
+            ..Default::default()
+        };
+        let before = post_cursor(None, Some("t3_y"));
+        let results = client.search_page(&params, &before).await.unwrap();
+        assert_eq!(results.after, None);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests[0].url.query(), Some("sort=new&limit=2&after=t3_abc"));
+        assert!(requests[1].url.query().unwrap().ends_with("&before=t3_y"));
+    }
+
+    #[tokio::test]
+    async fn test_subreddit_traffic() {
+        let server = MockServer::start().await;
+        Mock::given(path("/r/mysub/about/traffic"))
+            .and(header("authorization", "Bearer tok"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "day": [[1700006400, 10, 30, 2], [1699920000, 8, 20, 0]],
+                "hour": [[1700006400, 3, 5]],
+                "month": [[1698796800, 100, 900], []],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/r/rust/about/traffic"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({ "error": 403 })))
+            .mount(&server)
+            .await;
+
+        let mut config = Config::default();
+        config.reddit.access_token = Some("tok".to_string());
+        let client = RedditClient::from_config(config, Some(server.uri())).unwrap();
+
+        let traffic = client.get_subreddit_traffic("r/mysub").await.unwrap();
+        assert_eq!(traffic.day.len(), 2);
+        assert_eq!(traffic.day[0].subscriptions, Some(2));
+        assert_eq!(traffic.hour[0].pageviews, 5);
+        assert_eq!(traffic.hour[0].subscriptions, None);
+        assert_eq!(traffic.month.len(), 1);
+
+        let err = client.get_subreddit_traffic("rust").await.unwrap_err();
+        assert!(matches!(err, RdtError::Forbidden(_)), "{:?}", err);
+
+        // Public clients are told to log in before any request is made
+        let err = mock_client(&server).get_subreddit_traffic("mysub").await.unwrap_err();
+        assert!(matches!(err, RdtError::NotAuthenticated));
+    }
+
+    #[tokio::test]
+    async fn test_moderated_empty_or_hidden() {
+        let server = MockServer::start().await;
+        Mock::given(path("/user/quiet/moderated_subreddits.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .mount(&server)
+            .await;
+        Mock::given(path("/user/private/moderated_subreddits.json"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        Mock::given(path("/user/spez/moderated_subreddits.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "kind": "ModeratedList",
+                "data": [{ "sr": "announcements", "subscribers": 5, "url": "/r/announcements/" }],
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        assert!(client.get_user_moderated("quiet").await.unwrap().is_empty());
+        assert!(client.get_user_moderated("private").await.unwrap().is_empty());
+        let moderated = client.get_user_moderated("u/spez").await.unwrap();
+        assert_eq!(moderated[0].name, "announcements");
+        assert_eq!(moderated[0].url, "https://reddit.com/r/announcements/");
+    }
+
+    #[tokio::test]
+    async fn test_user_trophies() {
+        let server = MockServer::start().await;
+        Mock::given(path("/user/spez/trophies.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "kind": "TrophyList",
+                "data": { "trophies": [
+                    { "kind": "t6", "data": { "name": "Verified Email", "description": null, "icon_70": "x" } },
+                    { "kind": "t6", "data": { "name": "15-Year Club", "granted_at": 1700000000 } },
+                ] },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/user/new_user/trophies.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "kind": "TrophyList",
+                "data": { "trophies": [] },
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let trophies = client.get_user_trophies("u/spez").await.unwrap();
+        assert_eq!(trophies.len(), 2);
+        assert_eq!(trophies[0].name, "Verified Email");
+        assert_eq!(trophies[0].granted_at, None);
+        assert_eq!(trophies[1].granted_at, Some(1700000000.0));
+        assert!(client.get_user_trophies("new_user").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_html_response_is_explained() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw("<!doctype html><html></html>", "text/html; charset=utf-8"),
+            )
+            .mount(&server)
+            .await;
+
+        let err = mock_client(&server).get_post("abc123").await.unwrap_err();
+        assert!(err.to_string().contains("got HTML"));
+    }
+
+    #[tokio::test]
+    async fn test_block_pages_are_explained() {
+        let server = MockServer::start().await;
+        Mock::given(path("/by_id/t3_abc123.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<html><h1>whoa there, pardner!</h1></html>",
+                "text/html; charset=utf-8",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(path("/r/rust/about.json"))
+            .respond_with(ResponseTemplate::new(403).set_body_raw(
+                "<html>You've been blocked by network security.</html>",
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(path("/user/private/moderated_subreddits.json"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({ "reason": "private" })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        for err in [
+            client.get_post("abc123").await.unwrap_err(),
+            client.get_subreddit_info("rust").await.unwrap_err(),
+        ] {
+            assert!(err.to_string().contains("rdt auth login"), "{}", err);
+        }
+        // A JSON 403 is still an ordinary answer
+        assert!(client.get_user_moderated("private").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_429_is_rate_limited() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("x-ratelimit-reset", "30"))
+            .mount(&server)
+            .await;
+
+        let err = mock_client(&server).get_subreddit_info("rust").await.unwrap_err();
+        assert!(matches!(err, RdtError::RateLimited { reset_secs: Some(30) }));
+        assert_eq!(err.kind(), "RateLimited");
+        assert_eq!(err.to_string(), "Rate limited. Retry in 30s.");
+    }
+
+    #[tokio::test]
+    async fn test_missing_subreddit_suggests_names() {
+        let server = MockServer::start().await;
+        Mock::given(path("/r/rustt/about.json"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "error": 404 })))
+            .mount(&server)
+            .await;
+        let children: Vec<_> = ["rust", "rustlang"]
+            .iter()
+            .map(|name| {
+                let mut about = about_rust();
+                about["data"]["display_name"] = json!(name);
+                json!({ "kind": "t5", "data": about["data"] })
+            })
+            .collect();
+        Mock::given(path("/subreddits/search.json"))
+            .and(query_param("q", "rustt"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "kind": "Listing",
+                "data": { "after": null, "before": null, "children": children },
+            })))
+            .mount(&server)
+            .await;
+
+        let err = mock_client(&server).get_subreddit_info("rustt").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Subreddit r/rustt not found; did you mean r/rust, r/rustlang?"
+        );
+        assert_eq!(err.kind(), "SubredditNotFound");
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses_surface_as_api_errors() {
+        for status in [400, 401] {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(status).set_body_string("nope"))
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(about_rust()))
+                .mount(&server)
+                .await;
+
+            let client = mock_client(&server);
+            let err = client.get_subreddit_info("rust").await.unwrap_err();
+            match err {
+                RdtError::RedditApi(msg) => assert!(msg.starts_with(&format!("HTTP {}", status))),
+                other => panic!("unexpected error for {}: {:?}", status, other),
+            }
+            // Client errors aren't retried, so the queued 200 is still unused
+            assert_eq!(server.received_requests().await.unwrap().len(), 1);
+            // A failed request doesn't poison the client
+            assert!(client.get_subreddit_info("rust").await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transient_get_failures_are_retried() {
+        for first in [
+            ResponseTemplate::new(500),
+            ResponseTemplate::new(429).insert_header("retry-after", "0"),
+        ] {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(first)
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(about_rust()))
+                .mount(&server)
+                .await;
+
+            assert!(mock_client(&server).get_subreddit_info("rust").await.is_ok());
+            assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        }
+
+        // Retries are bounded; then the error surfaces as usual
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("down"))
+            .mount(&server)
+            .await;
+        let err = mock_client(&server).get_subreddit_info("rust").await.unwrap_err();
+        assert!(matches!(err, RdtError::RedditApi(ref msg) if msg.starts_with("HTTP 503")));
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1 + MAX_GET_RETRIES as usize);
+    }
+}
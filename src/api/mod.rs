@@ -1,2 +1,3 @@
+pub(crate) mod checkpoint;
 pub mod client;
 pub mod models;
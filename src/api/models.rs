@@ -1,4 +1,7 @@
+use crate::config::link_base;
+use crate::output::debug_note;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Reddit API listing response wrapper
 #[derive(Debug, Deserialize)]
@@ -12,6 +15,9 @@ pub struct ListingData<T> {
     pub after: Option<String>,
     pub before: Option<String>,
     pub children: Vec<Thing<T>>,
+    /// Only present on search responses requested with `include_facets`
+    #[serde(default)]
+    pub facets: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -60,11 +66,68 @@ pub struct Post {
     #[serde(default)]
     pub locked: bool,
     #[serde(default)]
+    pub post_hint: Option<String>,
+    #[serde(default)]
     pub link_flair_text: Option<String>,
     #[serde(default)]
+    pub total_awards_received: u64,
+    #[serde(default)]
     pub thumbnail: Option<String>,
     #[serde(default)]
     pub preview: Option<Preview>,
+    #[serde(default)]
+    pub media: Option<Media>,
+    #[serde(default)]
+    pub secure_media: Option<Media>,
+    /// Inline subreddit details, only present when requested with `sr_detail=true`
+    #[serde(default)]
+    pub sr_detail: Option<SrDetail>,
+    /// Fullname of the original post when this is a crosspost
+    #[serde(default)]
+    pub crosspost_parent: Option<String>,
+    #[serde(default)]
+    pub gallery_data: Option<GalleryData>,
+    /// Keyed by media id; entries vary by type and status, so kept as raw JSON
+    #[serde(default)]
+    pub media_metadata: Option<serde_json::Value>,
+}
+
+/// Embedded media for link posts (YouTube, Twitter, ...)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Media {
+    #[serde(default)]
+    pub oembed: Option<OEmbed>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OEmbed {
+    #[serde(default)]
+    pub provider_name: Option<String>,
+    #[serde(default)]
+    pub author_name: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SrDetail {
+    #[serde(default)]
+    pub subscribers: Option<u64>,
+    #[serde(default)]
+    pub over18: Option<bool>,
+}
+
+/// Ordered list of images in a gallery post
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GalleryData {
+    pub items: Vec<GalleryItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GalleryItem {
+    pub media_id: String,
 }
 
 /// Reddit preview images
@@ -80,7 +143,7 @@ pub struct PreviewImage {
     pub resolutions: Vec<ImageSource>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageSource {
     pub url: String,
     pub width: u32,
@@ -91,47 +154,207 @@ pub struct ImageSource {
 #[derive(Debug, Clone, Serialize)]
 pub struct PostSummary {
     pub id: String,
+    pub fullname: String,
     pub title: String,
     pub author: String,
     pub subreddit: String,
     pub url: String,
     pub score: i64,
+    pub upvote_ratio: f64,
     pub num_comments: u64,
-    pub created_utc: f64,
+    pub created_utc: Option<f64>, // None when Reddit sent 0 or a bogus future time
+    pub is_self: bool,
+    pub nsfw: bool,
+    pub spoiler: bool,
+    pub stickied: bool,
+    pub locked: bool,
+    pub flair: Option<String>,
+    pub awards: u64,
+    pub post_hint: Option<String>,
     pub thumbnail: Option<String>,
     pub image_url: Option<String>,
+    /// Gallery image URLs in display order (empty for non-gallery posts)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
+    /// Every preview size, narrowest first; the widest is normally `image_url`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub thumbnails: Vec<ImageSource>,
     pub selftext: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crosspost_parent: Option<String>,
+    /// From `sr_detail`, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subreddit_subscribers: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subreddit_nsfw: Option<bool>,
+    /// Other subreddits the same link was posted to (set by `dedupe_crossposts`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub also_in: Vec<String>,
+    /// External URL for link posts, used to spot duplicates
+    #[serde(skip_serializing)]
+    pub link_url: Option<String>,
+    /// Rich media metadata; only output on request (`post get --oembed`)
+    #[serde(skip_serializing)]
+    pub oembed: Option<OEmbed>,
 }
 
 impl From<Post> for PostSummary {
     fn from(p: Post) -> Self {
+        let images = gallery_images(&p);
+
+        let preview = p.preview.and_then(|preview| preview.images.into_iter().next());
+
         // Get the best image URL from preview if available
-        let image_url = p.preview.and_then(|preview| {
-            preview.images.first().map(|img| {
-                // HTML entity decode the URL (Reddit encodes &amp; etc)
-                img.source.url.replace("&amp;", "&")
+        // HTML entity decode the URL (Reddit encodes &amp; etc)
+        let image_url = preview
+            .as_ref()
+            .map(|img| img.source.url.replace("&amp;", "&"))
+            .or_else(|| images.first().cloned());
+
+        let thumbnails = preview
+            .map(|img| {
+                let mut sizes = img.resolutions;
+                sizes.push(img.source);
+                for size in &mut sizes {
+                    size.url = size.url.replace("&amp;", "&");
+                }
+                sizes.sort_by_key(|size| size.width);
+                sizes
             })
-        });
+            .unwrap_or_default();
 
         // Only use thumbnail if it's a valid URL (not "self", "default", "nsfw", etc)
         let thumbnail = p.thumbnail.filter(|t| t.starts_with("http"));
 
+        let fullname = fullname_or("t3", p.name, &p.id);
+        let link_url = Some(p.url).filter(|u| !p.is_self && !u.is_empty());
+        let oembed = p
+            .secure_media
+            .or(p.media)
+            .and_then(|m| m.oembed)
+            .map(|mut o| {
+                o.thumbnail_url = o.thumbnail_url.map(|u| u.replace("&amp;", "&"));
+                o
+            });
+
         Self {
             id: p.id,
+            fullname,
             title: p.title,
             author: p.author,
             subreddit: p.subreddit,
-            url: format!("https://reddit.com{}", p.permalink),
+            url: format!("{}{}", link_base(), p.permalink),
             score: p.score,
+            upvote_ratio: p.upvote_ratio,
             num_comments: p.num_comments,
-            created_utc: p.created_utc,
+            created_utc: valid_timestamp(p.created_utc),
+            is_self: p.is_self,
+            nsfw: p.over_18,
+            spoiler: p.spoiler,
+            stickied: p.stickied,
+            locked: p.locked,
+            flair: p.link_flair_text.filter(|f| !f.is_empty()),
+            awards: p.total_awards_received,
+            post_hint: p.post_hint,
             thumbnail,
             image_url,
+            images,
+            thumbnails,
             selftext: p.selftext.filter(|s| !s.is_empty()),
+            crosspost_parent: p.crosspost_parent,
+            subreddit_subscribers: p.sr_detail.as_ref().and_then(|d| d.subscribers),
+            subreddit_nsfw: p.sr_detail.as_ref().and_then(|d| d.over18),
+            also_in: Vec::new(),
+            link_url,
+            oembed,
         }
     }
 }
 
+impl PostSummary {
+    /// The smallest preview that fills a `width` x `height` pixel area once scaled to
+    /// fit (i.e. it reaches the area's width or its height), or the largest one there is
+    pub fn best_fit(&self, width: u32, height: u32) -> Option<&ImageSource> {
+        self.thumbnails
+            .iter()
+            .find(|size| size.width >= width || size.height >= height)
+            .or_else(|| self.thumbnails.last())
+    }
+}
+
+/// Client-side post predicates applied after fetching
+#[derive(Debug, Clone, Default)]
+pub struct PostFilter {
+    pub min_comments: Option<u64>,
+}
+
+impl PostFilter {
+    pub fn is_empty(&self) -> bool {
+        self.min_comments.is_none()
+    }
+
+    pub fn matches(&self, post: &PostSummary) -> bool {
+        self.min_comments.is_none_or(|min| post.num_comments >= min)
+    }
+}
+
+/// Collapse crossposts and reposts of the same link into the first (highest-ranked)
+/// occurrence, recording the other subreddits in `also_in`
+pub fn dedupe_crossposts(posts: Vec<PostSummary>) -> Vec<PostSummary> {
+    let mut kept: Vec<PostSummary> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for post in posts {
+        let mut keys = vec![post
+            .crosspost_parent
+            .clone()
+            .unwrap_or_else(|| post.fullname.clone())];
+        if let Some(ref url) = post.link_url {
+            keys.push(format!("url:{}", url));
+        }
+
+        match keys.iter().find_map(|k| seen.get(k).copied()) {
+            Some(index) => {
+                let original = &mut kept[index];
+                if original.subreddit != post.subreddit
+                    && !original.also_in.contains(&post.subreddit)
+                {
+                    original.also_in.push(post.subreddit);
+                }
+                for key in keys {
+                    seen.entry(key).or_insert(index);
+                }
+            }
+            None => {
+                for key in keys {
+                    seen.insert(key, kept.len());
+                }
+                kept.push(post);
+            }
+        }
+    }
+    kept
+}
+
+/// Full-size image URLs for a gallery post, skipping media that failed processing
+fn gallery_images(p: &Post) -> Vec<String> {
+    let (Some(gallery), Some(metadata)) = (&p.gallery_data, &p.media_metadata) else {
+        return Vec::new();
+    };
+    gallery
+        .items
+        .iter()
+        .filter_map(|item| {
+            let source = &metadata[&item.media_id]["s"];
+            // Animated images carry "gif" instead of "u"
+            source["u"]
+                .as_str()
+                .or_else(|| source["gif"].as_str())
+                .map(|url| url.replace("&amp;", "&"))
+        })
+        .collect()
+}
+
 /// Reddit comment data
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Comment {
@@ -146,37 +369,86 @@ pub struct Comment {
     pub link_id: String,
     #[serde(default)]
     pub replies: serde_json::Value, // Can be Listing or empty string
+    /// "moderator" or "admin" when posted in an official capacity
+    #[serde(default)]
+    pub distinguished: Option<String>,
+    /// Written by the post's author
+    #[serde(default)]
+    pub is_submitter: bool,
+    #[serde(default)]
+    pub stickied: bool,
+    /// Folded by default on Reddit (low score, AutoMod, removed)
+    #[serde(default)]
+    pub collapsed: bool,
 }
 
 /// Simplified comment for output
 #[derive(Debug, Clone, Serialize)]
 pub struct CommentSummary {
     pub id: String,
+    pub fullname: String,
     pub author: String,
     pub body: String,
     pub score: i64,
-    pub created_utc: f64,
+    pub created_utc: Option<f64>, // None when Reddit sent 0 or a bogus future time
     pub depth: u32,
+    pub link_id: String,
+    pub permalink: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinguished: Option<String>,
+    pub is_submitter: bool,
+    pub stickied: bool,
+    pub collapsed: bool,
     pub reply_count: usize,
     pub replies: Vec<CommentSummary>, // Nested replies (loaded on demand)
+    #[serde(skip_serializing)] // TUI expand/collapse state
     pub expanded: bool,
 }
 
 impl CommentSummary {
     pub fn from_comment(c: Comment, include_replies: bool) -> Self {
-        let (reply_count, replies) = if include_replies {
-            parse_replies(&c.replies, c.depth.unwrap_or(0) + 1)
+        Self::build(c, include_replies, None, &mut NodeBudget::default())
+    }
+
+    /// Build a comment (and optionally its replies), drawing every reply node from
+    /// `budget`. The caller is expected to have already taken a node for `c` itself.
+    /// With `min_score`, the subtree under any comment scoring below it isn't built;
+    /// those replies are still counted in `reply_count`.
+    pub fn build(
+        c: Comment,
+        include_replies: bool,
+        min_score: Option<i64>,
+        budget: &mut NodeBudget,
+    ) -> Self {
+        let expand = include_replies && min_score.is_none_or(|min| c.score >= min);
+        let (reply_count, replies) = if expand {
+            parse_replies(&c.replies, c.depth.unwrap_or(0) + 1, min_score, budget)
         } else {
             (count_replies(&c.replies), Vec::new())
         };
 
+        let fullname = fullname_or("t1", c.name, &c.id);
+        let permalink = format!(
+            "{}/comments/{}/_/{}",
+            link_base(),
+            c.link_id.trim_start_matches("t3_"),
+            c.id
+        );
+
         Self {
             id: c.id,
+            fullname,
             author: c.author,
             body: c.body,
             score: c.score,
-            created_utc: c.created_utc,
+            created_utc: valid_timestamp(c.created_utc),
             depth: c.depth.unwrap_or(0),
+            link_id: c.link_id,
+            permalink,
+            distinguished: c.distinguished,
+            is_submitter: c.is_submitter,
+            stickied: c.stickied,
+            collapsed: c.collapsed,
             reply_count,
             replies,
             expanded: false,
@@ -184,6 +456,116 @@ impl CommentSummary {
     }
 }
 
+/// Comments by the post's author (`is_submitter`) anywhere in the tree, in thread
+/// order, each with the comment it replies to (None for top-level comments)
+pub fn submitter_comments(
+    comments: &[CommentSummary],
+) -> Vec<(Option<&CommentSummary>, &CommentSummary)> {
+    fn walk<'a>(
+        comments: &'a [CommentSummary],
+        parent: Option<&'a CommentSummary>,
+        found: &mut Vec<(Option<&'a CommentSummary>, &'a CommentSummary)>,
+    ) {
+        for comment in comments {
+            if comment.is_submitter {
+                found.push((parent, comment));
+            }
+            walk(&comment.replies, Some(comment), found);
+        }
+    }
+
+    let mut found = Vec::new();
+    walk(comments, None, &mut found);
+    found
+}
+
+/// A post's comments along with the post itself, from one `/comments/<id>` request
+#[derive(Debug, Serialize)]
+pub struct CommentThread {
+    /// None if Reddit's response didn't include the post listing
+    pub post: Option<PostSummary>,
+    pub comments: Vec<CommentSummary>,
+    /// Whether `max_comments` left comments out
+    pub truncated: bool,
+}
+
+/// Remove comments Reddit shows collapsed, along with their replies, at every level
+pub fn drop_collapsed(comments: &mut Vec<CommentSummary>) {
+    comments.retain(|c| !c.collapsed);
+    for comment in comments {
+        drop_collapsed(&mut comment.replies);
+    }
+}
+
+/// Sort every level of a comment tree by `created_utc`, oldest first. Comments with
+/// no valid time go last; ties keep Reddit's order.
+pub fn sort_oldest_first(comments: &mut [CommentSummary]) {
+    comments.sort_by(|a, b| {
+        let time = |c: &CommentSummary| c.created_utc.unwrap_or(f64::INFINITY);
+        time(a).total_cmp(&time(b))
+    });
+    for comment in comments {
+        sort_oldest_first(&mut comment.replies);
+    }
+}
+
+/// Allowed clock skew before a future timestamp is treated as invalid
+const MAX_FUTURE_SKEW_SECS: f64 = 86400.0;
+
+/// Whether a Reddit `created_utc` looks like a real time (not 0/negative or far in the future)
+pub fn is_valid_timestamp(ts: f64) -> bool {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(f64::MAX);
+    ts > 0.0 && ts <= now + MAX_FUTURE_SKEW_SECS
+}
+
+fn valid_timestamp(ts: f64) -> Option<f64> {
+    is_valid_timestamp(ts).then_some(ts)
+}
+
+/// Use Reddit's `name` (e.g. `t3_abc123`), or build it from the kind prefix and id
+fn fullname_or(kind: &str, name: String, id: &str) -> String {
+    if name.is_empty() {
+        format!("{}_{}", kind, id)
+    } else {
+        name
+    }
+}
+
+/// Cap on the number of comment nodes built across a whole tree (`--max-comments`)
+#[derive(Debug, Default)]
+pub struct NodeBudget {
+    remaining: Option<usize>,
+    /// Set once a node had to be left out because the cap was reached
+    pub truncated: bool,
+}
+
+impl NodeBudget {
+    pub fn new(max: Option<usize>) -> Self {
+        Self {
+            remaining: max,
+            truncated: false,
+        }
+    }
+
+    /// Claim one node. Returns false (and marks the tree truncated) once the cap is hit.
+    pub fn take(&mut self) -> bool {
+        match &mut self.remaining {
+            None => true,
+            Some(0) => {
+                self.truncated = true;
+                false
+            }
+            Some(n) => {
+                *n -= 1;
+                true
+            }
+        }
+    }
+}
+
 fn count_replies(replies: &serde_json::Value) -> usize {
     if let Some(obj) = replies.as_object() {
         if let Some(data) = obj.get("data") {
@@ -197,19 +579,28 @@ fn count_replies(replies: &serde_json::Value) -> usize {
     0
 }
 
-fn parse_replies(replies: &serde_json::Value, depth: u32) -> (usize, Vec<CommentSummary>) {
+fn parse_replies(
+    replies: &serde_json::Value,
+    depth: u32,
+    min_score: Option<i64>,
+    budget: &mut NodeBudget,
+) -> (usize, Vec<CommentSummary>) {
     let mut result = Vec::new();
+    let mut dropped = 0;
+    let mut over_budget = 0;
     if let Some(obj) = replies.as_object() {
         if let Some(data) = obj.get("data") {
             if let Some(children) = data.get("children") {
                 if let Some(arr) = children.as_array() {
                     for child in arr {
                         if child.get("kind") == Some(&serde_json::json!("t1")) {
-                            if let Some(data) = child.get("data") {
-                                if let Ok(mut comment) = serde_json::from_value::<Comment>(data.clone()) {
+                            match child.get("data").and_then(parse_comment) {
+                                Some(_) if !budget.take() => over_budget += 1,
+                                Some(mut comment) => {
                                     comment.depth = Some(depth);
-                                    result.push(CommentSummary::from_comment(comment, true));
+                                    result.push(CommentSummary::build(comment, true, min_score, budget));
                                 }
+                                None => dropped += 1,
                             }
                         }
                     }
@@ -217,7 +608,23 @@ fn parse_replies(replies: &serde_json::Value, depth: u32) -> (usize, Vec<Comment
             }
         }
     }
-    (result.len(), result)
+    if dropped > 0 {
+        debug_note(&format!("Dropped {} malformed replies at depth {}", dropped, depth));
+    }
+    // Replies left out by the budget still exist, so they count toward `reply_count`
+    (result.len() + over_budget, result)
+}
+
+/// Deserialize a comment, noting (rather than silently swallowing) malformed data
+pub fn parse_comment(data: &serde_json::Value) -> Option<Comment> {
+    match serde_json::from_value::<Comment>(data.clone()) {
+        Ok(comment) => Some(comment),
+        Err(e) => {
+            let id = data.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
+            debug_note(&format!("Failed to parse comment {}: {}", id, e));
+            None
+        }
+    }
 }
 
 impl From<Comment> for CommentSummary {
@@ -240,6 +647,14 @@ pub struct Subreddit {
     pub created_utc: f64,
     pub over18: bool,
     pub url: String,
+    /// Full sidebar markdown
+    #[serde(default)]
+    pub description: Option<String>,
+    /// "any", "link" or "self"
+    #[serde(default)]
+    pub submission_type: Option<String>,
+    #[serde(default)]
+    pub lang: Option<String>,
 }
 
 /// Simplified subreddit for output
@@ -252,6 +667,146 @@ pub struct SubredditSummary {
     pub active_users: Option<u64>,
     pub nsfw: bool,
     pub url: String,
+    pub created_utc: Option<f64>,
+    pub sidebar: Option<String>,
+    pub submission_type: Option<String>,
+    pub lang: Option<String>,
+}
+
+/// Subreddit search results (`search_type = "subreddit"`)
+#[derive(Debug, Serialize)]
+pub struct SubredditSearchResults {
+    pub query: String,
+    pub subreddits: Vec<SubredditSummary>,
+    pub count: usize,
+}
+
+/// Entry from `/user/<name>/moderated_subreddits`
+#[derive(Debug, Deserialize)]
+pub struct ModeratedSubreddit {
+    pub sr: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub subscribers: u64,
+    #[serde(default)]
+    pub over_18: bool,
+    #[serde(default)]
+    pub url: String,
+}
+
+/// Simplified moderated subreddit for output
+#[derive(Debug, Serialize)]
+pub struct ModeratedSummary {
+    pub name: String,
+    pub title: String,
+    pub subscribers: u64,
+    pub nsfw: bool,
+    pub url: String,
+}
+
+impl From<ModeratedSubreddit> for ModeratedSummary {
+    fn from(m: ModeratedSubreddit) -> Self {
+        Self {
+            name: m.sr,
+            title: m.title,
+            subscribers: m.subscribers,
+            nsfw: m.over_18,
+            url: format!("{}{}", link_base(), m.url),
+        }
+    }
+}
+
+/// A post, comment or subreddit from `/api/info`, tagged with its `kind`
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum InfoItem {
+    Post(Box<PostSummary>),
+    Comment(CommentSummary),
+    Subreddit(SubredditSummary),
+}
+
+impl InfoItem {
+    /// Parse one `/api/info` child by its kind (t3, t1 or t5); None for anything else
+    pub fn from_thing(thing: Thing<serde_json::Value>) -> Option<Self> {
+        match thing.kind.as_str() {
+            "t3" => serde_json::from_value::<Post>(thing.data)
+                .ok()
+                .map(|p| InfoItem::Post(Box::new(p.into()))),
+            "t1" => parse_comment(&thing.data).map(|c| InfoItem::Comment(c.into())),
+            "t5" => serde_json::from_value::<Subreddit>(thing.data)
+                .ok()
+                .map(|s| InfoItem::Subreddit(s.into())),
+            _ => None,
+        }
+    }
+}
+
+/// Response from `/r/<name>/about/traffic`. Each row is
+/// `[timestamp, uniques, pageviews]`, plus `subscriptions` for daily rows.
+#[derive(Debug, Deserialize)]
+pub struct Traffic {
+    #[serde(default)]
+    pub day: Vec<Vec<i64>>,
+    #[serde(default)]
+    pub hour: Vec<Vec<i64>>,
+    #[serde(default)]
+    pub month: Vec<Vec<i64>>,
+}
+
+/// One period of subreddit traffic
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TrafficPoint {
+    pub timestamp: i64,
+    pub uniques: i64,
+    pub pageviews: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscriptions: Option<i64>,
+}
+
+/// Subreddit traffic for output, newest period first (as Reddit sends it)
+#[derive(Debug, Serialize)]
+pub struct TrafficSummary {
+    pub subreddit: String,
+    pub day: Vec<TrafficPoint>,
+    pub hour: Vec<TrafficPoint>,
+    pub month: Vec<TrafficPoint>,
+}
+
+impl TrafficSummary {
+    pub fn new(subreddit: String, traffic: Traffic) -> Self {
+        // Rows too short to hold the three required values are dropped
+        let points = |rows: Vec<Vec<i64>>| -> Vec<TrafficPoint> {
+            rows.into_iter()
+                .filter_map(|row| match row[..] {
+                    [timestamp, uniques, pageviews, ref rest @ ..] => Some(TrafficPoint {
+                        timestamp,
+                        uniques,
+                        pageviews,
+                        subscriptions: rest.first().copied(),
+                    }),
+                    _ => None,
+                })
+                .collect()
+        };
+        Self {
+            subreddit,
+            day: points(traffic.day),
+            hour: points(traffic.hour),
+            month: points(traffic.month),
+        }
+    }
+}
+
+/// Entry from `/user/<name>/trophies`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Trophy {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Unix time; Reddit leaves it out for most trophies
+    #[serde(default)]
+    pub granted_at: Option<f64>,
 }
 
 impl From<Subreddit> for SubredditSummary {
@@ -263,7 +818,11 @@ impl From<Subreddit> for SubredditSummary {
             subscribers: s.subscribers,
             active_users: s.active_user_count,
             nsfw: s.over18,
-            url: format!("https://reddit.com{}", s.url),
+            url: format!("{}{}", link_base(), s.url),
+            created_utc: valid_timestamp(s.created_utc),
+            sidebar: s.description.filter(|d| !d.is_empty()),
+            submission_type: s.submission_type,
+            lang: s.lang,
         }
     }
 }
@@ -291,7 +850,7 @@ pub struct UserSummary {
     pub link_karma: i64,
     pub comment_karma: i64,
     pub total_karma: i64,
-    pub created_utc: f64,
+    pub created_utc: Option<f64>, // None when Reddit sent 0 or a bogus future time
     pub is_gold: bool,
 }
 
@@ -302,12 +861,61 @@ impl From<User> for UserSummary {
             link_karma: u.link_karma,
             comment_karma: u.comment_karma,
             total_karma: u.link_karma + u.comment_karma,
-            created_utc: u.created_utc,
+            created_utc: valid_timestamp(u.created_utc),
             is_gold: u.is_gold,
         }
     }
 }
 
+/// Where a listing request starts: after or before a post's fullname (`t3_...`).
+/// The default starts at the top of the listing.
+#[derive(Debug, Clone, Default)]
+pub struct PageCursor {
+    pub after: Option<String>,
+    pub before: Option<String>,
+}
+
+/// Posts from a listing, with the cursors for the pages on either side
+#[derive(Debug, Default, Serialize)]
+pub struct PostPage {
+    pub posts: Vec<PostSummary>,
+    pub count: usize,
+    /// Pass to `--after` for the next page; null at the end of the listing
+    pub after: Option<String>,
+    /// Pass to `--before` for the previous page, when Reddit gives one
+    pub before: Option<String>,
+}
+
+impl PostPage {
+    pub fn new(posts: Vec<PostSummary>, after: Option<String>, before: Option<String>) -> Self {
+        Self {
+            count: posts.len(),
+            posts,
+            after,
+            before,
+        }
+    }
+}
+
+/// Posts newer than a known one, newest first
+#[derive(Debug, Default, Serialize)]
+pub struct NewPosts {
+    pub posts: Vec<PostSummary>,
+    /// False when paging stopped at the limit or page cap, or the known post never showed
+    /// up (deleted or gone from "new"); older unseen posts may then be missing
+    pub reached_since: bool,
+}
+
+/// A post just created with `post submit`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmittedPost {
+    pub id: String,
+    #[serde(rename(deserialize = "name"))]
+    pub fullname: String,
+    #[serde(rename(deserialize = "url"))]
+    pub permalink: String,
+}
+
 /// Search results wrapper
 #[derive(Debug, Serialize)]
 pub struct SearchResults {
@@ -316,4 +924,297 @@ pub struct SearchResults {
     pub sort: String,
     pub posts: Vec<PostSummary>,
     pub count: usize,
+    /// Pass to `--after` for the next page; null when there are no more results
+    pub after: Option<String>,
+    /// Pass to `--before` for the previous page, when Reddit gives one
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related_subreddits: Option<Vec<String>>,
+    /// Newest post id, for polling with `search --since-id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub newest_id: Option<String>,
+}
+
+impl SearchResults {
+    /// Keep only posts listed before `since_id` (newer, for the "new" sort) and record
+    /// the newest id, which stays `since_id` when nothing is newer
+    pub fn keep_newer_than(&mut self, since_id: &str) {
+        if let Some(pos) = self.posts.iter().position(|p| p.id == since_id) {
+            self.posts.truncate(pos);
+        }
+        self.count = self.posts.len();
+        self.newest_id = Some(
+            self.posts
+                .first()
+                .map_or(since_id.to_string(), |p| p.id.clone()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn comment_json(id: &str, replies: serde_json::Value) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": format!("t1_{}", id),
+            "author": "someone",
+            "body": "hello",
+            "score": 1,
+            "created_utc": 0.0,
+            "parent_id": "t3_post",
+            "link_id": "t3_post",
+            "replies": replies,
+        })
+    }
+
+    #[test]
+    fn test_gallery_images_in_order() {
+        let post: Post = serde_json::from_value(json!({
+            "id": "g1",
+            "gallery_data": { "items": [{ "media_id": "b" }, { "media_id": "x" }, { "media_id": "a" }] },
+            "media_metadata": {
+                "a": { "status": "valid", "s": { "u": "https://i.redd.it/a.jpg?w=1&amp;s=2" } },
+                "b": { "status": "valid", "s": { "gif": "https://i.redd.it/b.gif" } },
+                "x": { "status": "failed" },
+            },
+        }))
+        .unwrap();
+
+        let summary = PostSummary::from(post);
+        assert_eq!(
+            summary.images,
+            vec!["https://i.redd.it/b.gif", "https://i.redd.it/a.jpg?w=1&s=2"]
+        );
+        assert_eq!(summary.image_url.as_deref(), Some("https://i.redd.it/b.gif"));
+    }
+
+    #[test]
+    fn test_preview_best_fit() {
+        let size = |w: u32| json!({ "url": format!("https://i.redd.it/p.jpg?width={}&amp;s=x", w), "width": w, "height": w });
+        let post: Post = serde_json::from_value(json!({
+            "id": "p1",
+            "preview": { "images": [{ "source": size(3000), "resolutions": [size(108), size(640), size(320)] }] },
+        }))
+        .unwrap();
+
+        let summary = PostSummary::from(post);
+        let widths: Vec<u32> = summary.thumbnails.iter().map(|s| s.width).collect();
+        assert_eq!(widths, vec![108, 320, 640, 3000]);
+        assert_eq!(summary.image_url.as_deref(), Some("https://i.redd.it/p.jpg?width=3000&s=x"));
+
+        assert_eq!(summary.best_fit(300, 5000).unwrap().width, 320);
+        assert_eq!(summary.best_fit(640, 5000).unwrap().width, 640);
+        assert_eq!(summary.best_fit(5000, 5000).unwrap().width, 3000);
+        // A short, wide panel is filled by a smaller (square) preview
+        assert_eq!(summary.best_fit(2000, 300).unwrap().width, 320);
+        assert!(summary.best_fit(300, 300).unwrap().url.ends_with("&s=x"));
+    }
+
+    #[test]
+    fn test_keep_newer_than() {
+        let posts: Vec<PostSummary> = ["c", "b", "a"]
+            .iter()
+            .map(|id| PostSummary::from(serde_json::from_value::<Post>(json!({ "id": id })).unwrap()))
+            .collect();
+        let mut results = SearchResults {
+            query: "rust".to_string(),
+            subreddit: None,
+            sort: "new".to_string(),
+            count: posts.len(),
+            posts,
+            after: None,
+            before: None,
+            related_subreddits: None,
+            newest_id: None,
+        };
+
+        results.keep_newer_than("b");
+        assert_eq!(results.count, 1);
+        assert_eq!(results.newest_id.as_deref(), Some("c"));
+
+        results.keep_newer_than("c");
+        assert_eq!(results.count, 0);
+        assert_eq!(results.newest_id.as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_dedupe_crossposts() {
+        let post = |id: &str, subreddit: &str, extra: serde_json::Value| {
+            let mut value = json!({ "id": id, "name": format!("t3_{}", id), "subreddit": subreddit });
+            value.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            PostSummary::from(serde_json::from_value::<Post>(value).unwrap())
+        };
+        let posts = vec![
+            post("a", "news", json!({ "url": "https://example.com/story" })),
+            post("b", "worldnews", json!({ "crosspost_parent": "t3_a", "url": "/r/news/comments/a" })),
+            post("c", "politics", json!({ "url": "https://example.com/story" })),
+            post("d", "rust", json!({ "is_self": true })),
+        ];
+
+        let kept = dedupe_crossposts(posts);
+        let ids: Vec<&str> = kept.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "d"]);
+        assert_eq!(kept[0].also_in, vec!["worldnews", "politics"]);
+    }
+
+    #[test]
+    fn test_empty_string_replies() {
+        let comment = parse_comment(&comment_json("a", json!(""))).unwrap();
+        let summary = CommentSummary::from_comment(comment, true);
+        assert_eq!(summary.reply_count, 0);
+        assert!(summary.replies.is_empty());
+        assert_eq!(summary.fullname, "t1_a");
+        assert_eq!(summary.permalink, "https://reddit.com/comments/post/_/a");
+        assert!(!summary.is_submitter && !summary.stickied);
+        assert_eq!(summary.distinguished, None);
+        // created_utc of 0 means "unknown", not 1970
+        assert_eq!(summary.created_utc, None);
+    }
+
+    #[test]
+    fn test_listing_replies_skip_malformed() {
+        let replies = json!({
+            "kind": "Listing",
+            "data": {
+                "children": [
+                    { "kind": "t1", "data": comment_json("b", json!("")) },
+                    { "kind": "t1", "data": { "id": "broken" } },
+                    { "kind": "more", "data": {} },
+                ]
+            }
+        });
+        let comment = parse_comment(&comment_json("a", replies)).unwrap();
+        let summary = CommentSummary::from_comment(comment, true);
+        assert_eq!(summary.reply_count, 1);
+        assert_eq!(summary.replies[0].id, "b");
+        assert_eq!(summary.replies[0].depth, 1);
+    }
+
+    #[test]
+    fn test_submitter_comments_with_parents() {
+        let mut answer = comment_json("answer", json!(""));
+        answer["is_submitter"] = json!(true);
+        let question = comment_json("question", json!({ "kind": "Listing", "data": { "children": [{ "kind": "t1", "data": answer }] } }));
+        let mut intro = comment_json("intro", json!(""));
+        intro["is_submitter"] = json!(true);
+
+        let comments: Vec<CommentSummary> = [intro, question]
+            .iter()
+            .map(|c| CommentSummary::from_comment(parse_comment(c).unwrap(), true))
+            .collect();
+        let found: Vec<(Option<&str>, &str)> = submitter_comments(&comments)
+            .into_iter()
+            .map(|(parent, c)| (parent.map(|p| p.id.as_str()), c.id.as_str()))
+            .collect();
+        assert_eq!(found, vec![(None, "intro"), (Some("question"), "answer")]);
+    }
+
+    #[test]
+    fn test_sort_oldest_first_at_every_level() {
+        let at = |id: &str, time: f64, replies: serde_json::Value| {
+            let mut c = comment_json(id, replies);
+            c["created_utc"] = json!(time);
+            json!({ "kind": "t1", "data": c })
+        };
+        let listing = |children: Vec<serde_json::Value>| {
+            json!({ "kind": "Listing", "data": { "children": children } })
+        };
+        let late = at("late", 1_600_000_300.0, listing(vec![
+            at("r2", 1_600_000_500.0, json!("")),
+            at("undated", 0.0, json!("")),
+            at("r1", 1_600_000_400.0, json!("")),
+        ]));
+        let early = at("early", 1_600_000_100.0, json!(""));
+
+        let mut comments: Vec<CommentSummary> = [late, early]
+            .iter()
+            .map(|c| CommentSummary::from_comment(parse_comment(&c["data"]).unwrap(), true))
+            .collect();
+        sort_oldest_first(&mut comments);
+
+        let ids = |cs: &[CommentSummary]| cs.iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&comments), ["early", "late"]);
+        assert_eq!(ids(&comments[1].replies), ["r1", "r2", "undated"]);
+    }
+
+    #[test]
+    fn test_drop_collapsed_removes_subtrees() {
+        let mut hidden = comment_json("hidden", json!({ "kind": "Listing", "data": { "children": [
+            { "kind": "t1", "data": comment_json("under_hidden", json!("")) },
+        ] } }));
+        hidden["collapsed"] = json!(true);
+        let parent = comment_json("parent", json!({ "kind": "Listing", "data": { "children": [
+            { "kind": "t1", "data": hidden },
+            { "kind": "t1", "data": comment_json("shown", json!("")) },
+        ] } }));
+
+        let mut comments = vec![CommentSummary::from_comment(parse_comment(&parent).unwrap(), true)];
+        assert!(comments[0].replies[0].collapsed);
+        drop_collapsed(&mut comments);
+        let reply_ids: Vec<&str> = comments[0].replies.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(reply_ids, ["shown"]);
+    }
+
+    #[test]
+    fn test_tui_state_not_serialized() {
+        let comment = parse_comment(&comment_json("a", json!(""))).unwrap();
+        let mut summary = CommentSummary::from_comment(comment, true);
+        summary.expanded = true;
+        let value = serde_json::to_value(&summary).unwrap();
+        assert!(value.get("expanded").is_none());
+    }
+
+    /// Build a single chain `depth` levels deep, with scores counting down from `depth`
+    fn deep_chain(depth: i64) -> serde_json::Value {
+        let mut node = json!("");
+        for score in 1..=depth {
+            let mut child = comment_json(&format!("c{}", score), node);
+            child["score"] = json!(score);
+            node = json!({ "kind": "Listing", "data": { "children": [{ "kind": "t1", "data": child }] } });
+        }
+        node["data"]["children"][0]["data"].clone()
+    }
+
+    #[test]
+    fn test_pruned_parse_stops_below_threshold() {
+        // Root scores 50, then 49, 48, ... down the chain
+        let root = parse_comment(&deep_chain(50)).unwrap();
+        let full = CommentSummary::from_comment(parse_comment(&deep_chain(50)).unwrap(), true);
+        let pruned = CommentSummary::build(root, true, Some(45), &mut NodeBudget::default());
+
+        fn depth_of(c: &CommentSummary) -> usize {
+            1 + c.replies.first().map_or(0, depth_of)
+        }
+        assert_eq!(depth_of(&full), 50);
+
+        // 50..=45 are expanded, so the score-44 comment is the last one built
+        assert_eq!(depth_of(&pruned), 7);
+        let mut leaf = &pruned;
+        while let Some(next) = leaf.replies.first() {
+            leaf = next;
+        }
+        assert_eq!(leaf.score, 44);
+        assert_eq!(leaf.reply_count, 1);
+        assert!(leaf.replies.is_empty());
+    }
+
+    #[test]
+    fn test_node_budget_caps_whole_tree() {
+        let mut budget = NodeBudget::new(Some(10));
+        assert!(budget.take()); // the root
+        let root = CommentSummary::build(parse_comment(&deep_chain(50)).unwrap(), true, None, &mut budget);
+
+        fn count(c: &CommentSummary) -> usize {
+            1 + c.replies.iter().map(count).sum::<usize>()
+        }
+        assert_eq!(count(&root), 10);
+        assert!(budget.truncated);
+
+        let mut unlimited = NodeBudget::new(None);
+        CommentSummary::build(parse_comment(&deep_chain(5)).unwrap(), true, None, &mut unlimited);
+        assert!(!unlimited.truncated);
+    }
 }
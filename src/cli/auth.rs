@@ -1,8 +1,10 @@
+use crate::api::client::rate_limit_reset_secs;
 use crate::config::Config;
 use crate::error::{RdtError, Result};
 use rand::Rng;
 use std::io::{BufRead, BufReader, Write};
-use std::net::TcpListener;
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
 use url::Url;
 
 const REDDIT_AUTH_URL: &str = "https://www.reddit.com/api/v1/authorize";
@@ -12,9 +14,18 @@ const REDIRECT_URI: &str = "http://127.0.0.1:8484";
 // Reddit OAuth scopes needed for read/write operations
 const SCOPES: &str = "read submit vote identity";
 
+// How long to wait for the user to finish the browser flow
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(120);
+
+// Longest Retry-After we'll honor on the token endpoint before giving up
+const MAX_TOKEN_RETRY_WAIT_SECS: u64 = 10;
+
 pub async fn login() -> Result<()> {
     let mut config = Config::load()?;
 
+    // Tokens are saved at the end, so don't send the user through the browser for nothing
+    config.ensure_writable()?;
+
     // Check if client_id is configured
     let client_id = config
         .reddit
@@ -66,23 +77,8 @@ pub async fn login() -> Result<()> {
         eprintln!("Browser open error: {}", e);
     }
 
-    // Wait for callback
-    let (mut stream, _) = listener.accept()
-        .map_err(|e| RdtError::Auth(format!("Failed to accept connection: {}", e)))?;
-
-    let mut reader = BufReader::new(&stream);
-    let mut request_line = String::new();
-    reader.read_line(&mut request_line)
-        .map_err(|e| RdtError::Auth(format!("Failed to read request: {}", e)))?;
-
-    // Parse the callback URL to get the authorization code
-    let request_path = request_line
-        .split_whitespace()
-        .nth(1)
-        .ok_or_else(|| RdtError::Auth("Invalid callback request".to_string()))?;
-
-    let callback_url = Url::parse(&format!("http://localhost{}", request_path))
-        .map_err(|e| RdtError::Auth(format!("Failed to parse callback URL: {}", e)))?;
+    // Wait for callback (listener is dropped, releasing the port, on timeout)
+    let (mut stream, callback_url) = wait_for_callback(&listener)?;
 
     // Check for error in callback
     if let Some(error) = callback_url.query_pairs().find(|(k, _)| k == "error") {
@@ -114,7 +110,7 @@ pub async fn login() -> Result<()> {
 
     // Exchange code for access token
     let client = reqwest::Client::new();
-    let token_response = client
+    let token_request = client
         .post(REDDIT_TOKEN_URL)
         .basic_auth(&client_id, Some("")) // For installed apps, password is empty string
         .header("User-Agent", config.user_agent())
@@ -122,10 +118,8 @@ pub async fn login() -> Result<()> {
             ("grant_type", "authorization_code"),
             ("code", &code),
             ("redirect_uri", REDIRECT_URI),
-        ])
-        .send()
-        .await
-        .map_err(|e| RdtError::Auth(format!("Token request failed: {}", e)))?;
+        ]);
+    let token_response = send_token_request(token_request).await?;
 
     if !token_response.status().is_success() {
         let error_text = token_response.text().await.unwrap_or_default();
@@ -157,7 +151,99 @@ pub async fn login() -> Result<()> {
     Ok(())
 }
 
-fn send_response(stream: &mut std::net::TcpStream, message: &str) {
+/// Accept connections until the OAuth redirect arrives or `CALLBACK_TIMEOUT` elapses.
+/// Requests without a `code` or `error` param (e.g. favicon) get a 404 and are skipped.
+fn wait_for_callback(listener: &TcpListener) -> Result<(TcpStream, Url)> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| RdtError::Auth(format!("Failed to configure local server: {}", e)))?;
+    let deadline = Instant::now() + CALLBACK_TIMEOUT;
+
+    loop {
+        match listener.accept() {
+            Ok((mut stream, _)) => {
+                // Browsers may open speculative connections that never send a request,
+                // so don't let a silent socket block the loop
+                stream
+                    .set_nonblocking(false)
+                    .and_then(|_| stream.set_read_timeout(Some(Duration::from_secs(5))))
+                    .map_err(|e| RdtError::Auth(format!("Failed to accept connection: {}", e)))?;
+
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+
+                // Parse the callback URL to get the authorization code
+                let callback_url = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|path| Url::parse(&format!("http://localhost{}", path)).ok());
+
+                match callback_url {
+                    Some(url) if url.query_pairs().any(|(k, _)| k == "code" || k == "error") => {
+                        return Ok((stream, url));
+                    }
+                    _ => send_not_found(&mut stream),
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                if Instant::now() >= deadline {
+                    return Err(RdtError::Auth("authorization timed out".to_string()));
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => {
+                return Err(RdtError::Auth(format!("Failed to accept connection: {}", e)));
+            }
+        }
+    }
+}
+
+/// Send a token endpoint request, retrying once if rate limited.
+/// Honors `Retry-After` up to `MAX_TOKEN_RETRY_WAIT_SECS` so login can't hang.
+async fn send_token_request(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    let retry = request.try_clone();
+    let response = request
+        .send()
+        .await
+        .map_err(|e| RdtError::Auth(format!("Token request failed: {}", e)))?;
+
+    if response.status() != 429 {
+        return Ok(response);
+    }
+
+    let reset_secs = rate_limit_reset_secs(response.headers());
+    let wait_secs = reset_secs.unwrap_or(1);
+
+    let retry = match retry {
+        Some(retry) if wait_secs <= MAX_TOKEN_RETRY_WAIT_SECS => retry,
+        _ => return Err(RdtError::RateLimited { reset_secs }),
+    };
+
+    tokio::time::sleep(Duration::from_secs(wait_secs)).await;
+
+    let response = retry
+        .send()
+        .await
+        .map_err(|e| RdtError::Auth(format!("Token request failed: {}", e)))?;
+
+    if response.status() == 429 {
+        return Err(RdtError::RateLimited {
+            reset_secs: rate_limit_reset_secs(response.headers()),
+        });
+    }
+    Ok(response)
+}
+
+fn send_not_found(stream: &mut TcpStream) {
+    let _ = stream.write_all(
+        b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    );
+}
+
+fn send_response(stream: &mut TcpStream, message: &str) {
     let response = format!(
         "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nConnection: close\r\n\r\n\
         <!DOCTYPE html><html><head><title>rdt</title></head>\
@@ -180,6 +266,7 @@ pub async fn status() -> Result<()> {
         "has_client_id": has_client_id,
         "has_refresh_token": has_refresh_token,
         "config_path": config.config_path().display().to_string(),
+        "profile": crate::config::profile(),
     }));
 
     Ok(())
@@ -0,0 +1,12 @@
+use crate::api::client::{thing_fullname, RedditClient};
+use crate::error::Result;
+use crate::output::{format_output, OutputOptions};
+
+/// Reply to a post or comment and print the new comment
+pub async fn reply(parent: &str, text: &str, output: &OutputOptions) -> Result<()> {
+    let client = RedditClient::new().await?;
+    let comment = client.reply(&thing_fullname(parent), text).await?;
+
+    format_output(&comment, output)?;
+    Ok(())
+}
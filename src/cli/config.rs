@@ -0,0 +1,218 @@
+use crate::api::client::RedditClient;
+use crate::config::Config;
+use crate::error::{RdtError, Result};
+use crate::output::{format_output, OutputOptions};
+use serde::Serialize;
+
+/// Spinner styles the TUI knows; anything else falls back to braille
+const SPINNERS: &[&str] = &["braille", "dots", "line", "none"];
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    message: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Check the config for the operations in `needs` ("auth", "ai"). Missing settings
+/// those operations rely on fail; otherwise they only warn. Unless `offline`, a stored
+/// access token is tried against /api/v1/me. Exits non-zero when any check fails.
+pub async fn validate(needs: &[String], offline: bool, output: &OutputOptions) -> Result<()> {
+    if let Some(unknown) = needs.iter().find(|n| !matches!(n.as_str(), "auth" | "ai")) {
+        return Err(RdtError::Config(format!(
+            "Unknown --for value '{}': use auth or ai",
+            unknown
+        )));
+    }
+    let needs_auth = needs.iter().any(|n| n == "auth");
+    let needs_ai = needs.iter().any(|n| n == "ai");
+
+    let (config_path, checks) = match Config::load() {
+        Ok(config) => {
+            let mut checks = config_checks(&config, needs_auth, needs_ai);
+            checks.push(token_check(&config, offline).await);
+            (Some(config.config_path()), checks)
+        }
+        Err(e) => {
+            let path = Config::config_dir().ok().map(|dir| dir.join("config.toml"));
+            (path, vec![Check::new("config_file", Status::Fail, e.to_string())])
+        }
+    };
+
+    let valid = !checks.iter().any(|c| c.status == Status::Fail);
+    let report = serde_json::json!({
+        "valid": valid,
+        "config_path": config_path.map(|p| p.display().to_string()),
+        "profile": crate::config::profile(),
+        "checks": checks,
+    });
+    format_output(&report, output)?;
+
+    if !valid {
+        return Err(RdtError::Config("validation failed (see checks)".to_string()));
+    }
+    Ok(())
+}
+
+/// Everything that can be checked without the network
+fn config_checks(config: &Config, needs_auth: bool, needs_ai: bool) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let missing = |required: bool| if required { Status::Fail } else { Status::Warn };
+
+    checks.push(if config.config_path().exists() {
+        Check::new("config_file", Status::Pass, "parsed")
+    } else {
+        Check::new("config_file", Status::Warn, "not found; using defaults")
+    });
+
+    checks.push(match config.reddit.client_id {
+        Some(_) => Check::new("client_id", Status::Pass, "set"),
+        None => Check::new(
+            "client_id",
+            missing(needs_auth),
+            "not set; needed for `rdt auth login`",
+        ),
+    });
+    checks.push(match config.reddit.access_token {
+        Some(_) => Check::new("access_token", Status::Pass, "set"),
+        None => Check::new(
+            "access_token",
+            missing(needs_auth),
+            "not logged in; only public endpoints are available",
+        ),
+    });
+
+    for (name, value) in [
+        ("api_base_url", &config.reddit.api_base_url),
+        ("public_base_url", &config.reddit.public_base_url),
+    ] {
+        if let Some(value) = value {
+            let ok = url::Url::parse(value).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+            checks.push(if ok {
+                Check::new(name, Status::Pass, value.as_str())
+            } else {
+                Check::new(name, Status::Fail, format!("'{}' is not an http(s) URL", value))
+            });
+        }
+    }
+
+    // User agent and extra headers are checked the same way the client builds them
+    checks.push(match RedditClient::with_config(config.clone()) {
+        Ok(_) => Check::new("http", Status::Pass, "user agent and headers are valid"),
+        Err(e) => Check::new("http", Status::Fail, e.to_string()),
+    });
+
+    checks.push(match config.aws.region {
+        Some(ref region)
+            if !region.is_empty()
+                && region.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') =>
+        {
+            Check::new("aws_region", Status::Pass, region.as_str())
+        }
+        Some(ref region) => Check::new(
+            "aws_region",
+            Status::Fail,
+            format!("'{}' doesn't look like an AWS region (e.g. us-east-1)", region),
+        ),
+        None => Check::new(
+            "aws_region",
+            if needs_ai { Status::Warn } else { Status::Pass },
+            "not set; defaults to us-east-1",
+        ),
+    });
+    checks.push(Check::new("bedrock_model_id", Status::Pass, config.bedrock_model_id()));
+
+    if needs_ai {
+        // The AWS SDK has more sources (SSO, instance roles), so this can only warn
+        let has_credentials = ["AWS_ACCESS_KEY_ID", "AWS_PROFILE", "AWS_WEB_IDENTITY_TOKEN_FILE"]
+            .iter()
+            .any(|var| std::env::var_os(var).is_some())
+            || dirs::home_dir().is_some_and(|home| home.join(".aws").exists());
+        checks.push(if has_credentials {
+            Check::new("aws_credentials", Status::Pass, "found")
+        } else {
+            Check::new(
+                "aws_credentials",
+                Status::Warn,
+                "none found in the environment or ~/.aws",
+            )
+        });
+    }
+
+    if let Some(ref spinner) = config.tui.spinner {
+        if !SPINNERS.contains(&spinner.as_str()) {
+            checks.push(Check::new(
+                "tui_spinner",
+                Status::Warn,
+                format!("unknown spinner '{}'; use {}", spinner, SPINNERS.join(", ")),
+            ));
+        }
+    }
+
+    checks
+}
+
+async fn token_check(config: &Config, offline: bool) -> Check {
+    if config.reddit.access_token.is_none() {
+        return Check::new("token", Status::Skip, "no access token");
+    }
+    if offline {
+        return Check::new("token", Status::Skip, "offline");
+    }
+    let me = match RedditClient::with_config(config.clone()) {
+        Ok(client) => client.get_me().await,
+        Err(e) => Err(e),
+    };
+    match me {
+        Ok(me) => Check::new("token", Status::Pass, format!("authenticated as u/{}", me.name)),
+        Err(e) => Check::new("token", Status::Fail, e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_of<'a>(checks: &'a [Check], name: &str) -> Option<&'a Status> {
+        checks.iter().find(|c| c.name == name).map(|c| &c.status)
+    }
+
+    #[test]
+    fn test_requirements_turn_warnings_into_failures() {
+        let mut config = Config::default();
+        let checks = config_checks(&config, false, false);
+        assert_eq!(status_of(&checks, "client_id"), Some(&Status::Warn));
+        assert_eq!(status_of(&checks, "aws_credentials"), None);
+
+        let checks = config_checks(&config, true, false);
+        assert_eq!(status_of(&checks, "client_id"), Some(&Status::Fail));
+        assert_eq!(status_of(&checks, "access_token"), Some(&Status::Fail));
+
+        config.reddit.client_id = Some("abc".to_string());
+        config.reddit.api_base_url = Some("oauth.reddit.com".to_string());
+        config.http.extra_headers.insert("User-Agent".to_string(), "x".to_string());
+        let checks = config_checks(&config, true, false);
+        assert_eq!(status_of(&checks, "client_id"), Some(&Status::Pass));
+        assert_eq!(status_of(&checks, "api_base_url"), Some(&Status::Fail));
+        assert_eq!(status_of(&checks, "http"), Some(&Status::Fail));
+    }
+}
@@ -0,0 +1,11 @@
+use crate::api::client::RedditClient;
+use crate::error::Result;
+use crate::output::{format_output, OutputOptions};
+
+pub async fn info(fullnames: &[String], output: &OutputOptions) -> Result<()> {
+    let client = RedditClient::new().await?;
+    let items = client.get_info(fullnames).await?;
+
+    format_output(&items, output)?;
+    Ok(())
+}
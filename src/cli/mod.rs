@@ -1,5 +1,23 @@
+use crate::api::client::MAX_LISTING_LIMIT;
+
 pub mod auth;
+pub mod comment;
+pub mod config;
+pub mod info;
+pub mod ping;
 pub mod post;
 pub mod search;
 pub mod subreddit;
 pub mod user;
+
+/// Warn on stderr that a command fetching a single page can't return `limit` items
+pub fn warn_if_capped(limit: u32) {
+    if limit > MAX_LISTING_LIMIT {
+        eprintln!("{}", serde_json::json!({
+            "warning": format!(
+                "limit {} exceeds Reddit's cap of {} per request; results are capped at {}",
+                limit, MAX_LISTING_LIMIT, MAX_LISTING_LIMIT
+            ),
+        }));
+    }
+}
@@ -0,0 +1,21 @@
+use crate::api::client::RedditClient;
+use crate::error::Result;
+use crate::output::{format_output, OutputOptions};
+use std::time::Instant;
+
+pub async fn ping(output: &OutputOptions) -> Result<()> {
+    let client = RedditClient::new().await?;
+
+    let started = Instant::now();
+    client.ping().await?;
+    let latency_ms = started.elapsed().as_millis();
+
+    let result = serde_json::json!({
+        "ok": true,
+        "authenticated": client.is_authenticated(),
+        "latency_ms": latency_ms,
+        "base_url": client.base_url(),
+    });
+    format_output(&result, output)?;
+    Ok(())
+}
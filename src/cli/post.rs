@@ -1,19 +1,210 @@
-use crate::api::client::RedditClient;
-use crate::error::Result;
-use crate::output::format_output;
+use crate::api::client::{extract_post_id, thing_fullname, RedditClient, BY_ID_BATCH};
+use crate::api::models::{
+    drop_collapsed, sort_oldest_first, submitter_comments, CommentSummary, PostSummary,
+};
+use crate::error::{RdtError, Result};
+use crate::output::{format_output, open_output, write_output, OutputOptions};
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
 
-pub async fn get(id: &str, format: &str) -> Result<()> {
+/// Create a text or link post and print its id and permalink
+pub async fn submit(
+    subreddit: &str,
+    title: &str,
+    text: Option<&str>,
+    url: Option<&str>,
+    output: &OutputOptions,
+) -> Result<()> {
     let client = RedditClient::new().await?;
-    let post = client.get_post(id).await?;
+    let post = client.submit_post(subreddit, title, text, url).await?;
 
-    format_output(&post, format)?;
+    format_output(&post, output)?;
     Ok(())
 }
 
-pub async fn comments(id: &str, sort: &str, limit: u32, format: &str) -> Result<()> {
+/// Vote on a post or comment; `direction` is up, down or clear
+pub async fn vote(id: &str, direction: &str, output: &OutputOptions) -> Result<()> {
+    let dir = match direction {
+        "up" => 1,
+        "down" => -1,
+        "clear" => 0,
+        _ => {
+            return Err(RdtError::Config(format!(
+                "Invalid direction '{}': use up, down or clear",
+                direction
+            )))
+        }
+    };
+    let fullname = thing_fullname(id);
+
     let client = RedditClient::new().await?;
-    let comments = client.get_comments(id, sort, limit).await?;
+    client.vote(&fullname, dir).await?;
 
-    format_output(&comments, format)?;
+    let result = serde_json::json!({
+        "status": "success",
+        "id": fullname,
+        "direction": direction,
+    });
+    format_output(&result, output)?;
     Ok(())
 }
+
+/// A single id outputs the post itself; several output an array (missing ids omitted)
+pub async fn get(ids: &[String], oembed: bool, output: &OutputOptions) -> Result<()> {
+    let client = RedditClient::new().await?;
+
+    if let [id] = ids {
+        let post = client.get_post(id).await?;
+        return format_output(&post_value(&post, oembed)?, output);
+    }
+
+    let posts = client.get_posts(ids).await?;
+    let values = posts
+        .iter()
+        .map(|post| post_value(post, oembed))
+        .collect::<Result<Vec<_>>>()?;
+    format_output(&values, output)
+}
+
+/// Read post ids/URLs from stdin, one per line, and stream the posts as NDJSON.
+/// Blank lines and repeated ids are skipped; each 100 new ids is one /by_id request.
+pub async fn get_stdin(oembed: bool, output: &OutputOptions) -> Result<()> {
+    if output.format == "table" {
+        return Err(RdtError::Config(
+            "--stdin streams NDJSON and can't be combined with --format table".to_string(),
+        ));
+    }
+    let client = RedditClient::new().await?;
+    let options = OutputOptions {
+        compact: true,
+        ..output.clone()
+    };
+    let mut writer = open_output(output)?;
+
+    let mut seen = HashSet::new();
+    let mut batch = Vec::new();
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        let line = lines.next().transpose()?;
+        if let Some(ref line) = line {
+            let id = extract_post_id(line.trim());
+            if !id.is_empty() && seen.insert(id.to_string()) {
+                batch.push(id.to_string());
+            }
+        }
+        // Flush full batches as they fill so downstream sees results before EOF
+        if batch.len() == BY_ID_BATCH || (line.is_none() && !batch.is_empty()) {
+            for post in client.get_posts(&batch).await? {
+                write_output(&mut writer, &post_value(&post, oembed)?, &options)?;
+            }
+            writer.flush()?;
+            batch.clear();
+        }
+        if line.is_none() {
+            return Ok(());
+        }
+    }
+}
+
+fn post_value(post: &PostSummary, oembed: bool) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(post)?;
+    if oembed {
+        value["oembed"] = serde_json::to_value(&post.oembed)?;
+    }
+    Ok(value)
+}
+
+/// The `post comments` command's flags, gathered by main.rs
+pub struct CommentsArgs {
+    pub id: String,
+    pub sort: String,
+    pub limit: u32,
+    pub tree: bool,
+    /// Drop comments (and their replies) scoring below this
+    pub prune_below: Option<i64>,
+    /// Fetch every comment, checkpointing progress to this state file
+    pub resume: Option<PathBuf>,
+    pub max_comments: Option<usize>,
+    pub op_only: bool,
+    /// With `op_only`, pair each OP comment with its parent
+    pub with_context: bool,
+    pub oldest_first: bool,
+    pub with_post: bool,
+    pub fold_collapsed: bool,
+}
+
+pub async fn comments(args: &CommentsArgs, output: &OutputOptions) -> Result<()> {
+    let client = RedditClient::new().await?;
+
+    let (post, mut comments, truncated) = match args.resume {
+        Some(ref state_path) => {
+            let comments = client
+                .get_all_comments_resumable(&args.id, &args.sort, state_path)
+                .await?;
+            (None, comments, false)
+        }
+        None => {
+            // OP answers are usually replies, so --op-only always loads the tree
+            let thread = client
+                .get_thread(
+                    &args.id,
+                    &args.sort,
+                    args.limit,
+                    args.tree || args.op_only,
+                    args.prune_below,
+                    args.max_comments,
+                )
+                .await?;
+            (thread.post, thread.comments, thread.truncated)
+        }
+    };
+
+    if args.fold_collapsed {
+        drop_collapsed(&mut comments);
+    }
+    if args.oldest_first {
+        sort_oldest_first(&mut comments);
+    }
+
+    let comments = if args.op_only {
+        op_comments(&comments, args.with_context)
+    } else {
+        serde_json::to_value(&comments)?
+    };
+
+    if args.with_post || args.max_comments.is_some() {
+        let mut result = serde_json::Map::new();
+        if args.with_post {
+            result.insert("post".to_string(), serde_json::to_value(&post)?);
+        }
+        result.insert("comments".to_string(), comments);
+        if args.max_comments.is_some() {
+            result.insert("truncated".to_string(), serde_json::json!(truncated));
+        }
+        format_output(&result, output)?;
+        return Ok(());
+    }
+
+    format_output(&comments, output)?;
+    Ok(())
+}
+
+/// The OP's comments as a flat list, or with `with_context` as
+/// `{"parent": ..., "comment": ...}` pairs (parent is null for top-level comments)
+fn op_comments(comments: &[CommentSummary], with_context: bool) -> serde_json::Value {
+    // Replies are dropped so each comment appears once rather than nested in its parent
+    let leaf = |c: &CommentSummary| CommentSummary {
+        replies: Vec::new(),
+        ..c.clone()
+    };
+    let found = submitter_comments(comments);
+    if with_context {
+        found
+            .into_iter()
+            .map(|(parent, c)| serde_json::json!({ "parent": parent.map(leaf), "comment": leaf(c) }))
+            .collect()
+    } else {
+        found.into_iter().map(|(_, c)| serde_json::json!(leaf(c))).collect()
+    }
+}
@@ -1,59 +1,283 @@
-use crate::api::client::RedditClient;
-use crate::error::Result;
+use crate::api::client::{normalize_listing_subreddit, RedditClient};
+use crate::api::models::{PageCursor, PostFilter, PostSummary};
+use crate::cli::warn_if_capped;
+use crate::error::{RdtError, Result};
 use crate::nlp::router::{NlpRouter, SearchParams};
-use crate::output::format_output;
+use crate::output::{format_output, ApiResponse, OutputOptions};
+use serde::Serialize;
+use crate::state::SavedSearches;
+use std::io::{BufRead, IsTerminal, Write};
 
 // CLI defaults (must match main.rs)
 const DEFAULT_SORT: &str = "relevance";
 const DEFAULT_TIME: &str = "all";
 const DEFAULT_LIMIT: u32 = 25;
 
-pub async fn search(
-    query: &str,
-    subreddit: Option<&str>,
-    search_type: &str,
-    sort: &str,
-    time: &str,
-    limit: u32,
-    format: &str,
-) -> Result<()> {
-    let router = NlpRouter::new();
+/// The `search` command's flags, gathered by main.rs
+pub struct SearchArgs {
+    pub query: String,
+    pub subreddit: Option<String>,
+    pub search_type: String,
+    pub sort: String,
+    pub time: String,
+    pub limit: u32,
+    pub region: Option<String>,
+    pub filter: PostFilter,
+    pub facets: bool,
+    pub sr_detail: bool,
+    pub count_only: bool,
+    pub debug_ai: bool,
+    pub explain: bool,
+    pub would_use_ai: bool,
+    pub interactive: bool,
+    /// With --interactive, print the chosen post as JSON instead of its URL
+    pub json: bool,
+    /// Only posts newer than this one (needs --sort new)
+    pub since_id: Option<String>,
+    /// Save the parsed query under this name
+    pub save: Option<String>,
+    /// Rerun this saved search
+    pub run: Option<String>,
+    pub cursor: PageCursor,
+    pub with_meta: bool,
+}
+
+pub async fn search(args: &SearchArgs, output: &OutputOptions) -> Result<()> {
+    // Check before searching so a pipeline doesn't wait on a network call just to fail
+    if args.interactive && !(std::io::stdin().is_terminal() && std::io::stderr().is_terminal()) {
+        return Err(RdtError::Config(
+            "--interactive needs a terminal on stdin and stderr".to_string(),
+        ));
+    }
+
+    let router = NlpRouter::new().with_debug_ai(args.debug_ai);
+
+    // Routing details go to stderr so stdout stays a single JSON document
+    if args.explain {
+        eprintln!("{}", serde_json::json!({
+            "explain": {
+                "needs_nlp": router.explain_needs_nlp(&args.query),
+                "explicit_subreddit": args.subreddit.is_some(),
+            }
+        }));
+    }
 
-    // If user provided explicit --subreddit flag, use explicit params
-    // Otherwise, try NLP parsing (pattern matching or AI)
-    let mut params = if subreddit.is_some() {
+    // Routing preview only - no Reddit or Bedrock calls
+    if args.would_use_ai {
+        let preview = serde_json::json!({
+            "needs_ai": router.needs_nlp(&args.query),
+            "pattern_matched": router.pattern_matches(&args.query),
+        });
+        format_output(&preview, output)?;
+        return Ok(());
+    }
+
+    // A saved search starts from the params parsed when it was saved.
+    // Otherwise, if user provided explicit --subreddit flag, use explicit params;
+    // else try NLP parsing (pattern matching or AI)
+    let mut params = if let Some(ref name) = args.run {
+        SavedSearches::load()?.get(name).cloned().ok_or_else(|| {
+            RdtError::Config(format!("No saved search named '{}' (see --list-saved)", name))
+        })?
+    } else if let Some(ref subreddit) = args.subreddit {
         // User explicitly specified subreddit, use as-is
         SearchParams {
-            query: query.to_string(),
-            subreddit: subreddit.map(String::from),
-            sort: sort.to_string(),
-            time: time.to_string(),
-            limit,
-            search_type: search_type.to_string(),
+            query: args.query.clone(),
+            subreddit: Some(normalize_listing_subreddit(subreddit)?),
+            sort: args.sort.clone(),
+            time: args.time.clone(),
+            limit: args.limit,
+            search_type: args.search_type.clone(),
+            region: None,
+            include_facets: false,
+            sr_detail: false,
             parse_method: None,
         }
     } else {
         // Try NLP parsing (pattern matching first, then AI if needed)
-        router.parse_query(query).await?
+        router.parse_query(&args.query).await?
     };
 
     // CLI flags override NLP-parsed values when explicitly set (not default)
-    if sort != DEFAULT_SORT {
-        params.sort = sort.to_string();
+    if args.sort != DEFAULT_SORT {
+        params.sort = args.sort.clone();
+    }
+    if args.time != DEFAULT_TIME {
+        params.time = args.time.clone();
+    }
+    if args.limit != DEFAULT_LIMIT {
+        params.limit = args.limit;
     }
-    if time != DEFAULT_TIME {
-        params.time = time.to_string();
+    if args.search_type != "posts" {
+        params.search_type = args.search_type.clone();
     }
-    if limit != DEFAULT_LIMIT {
-        params.limit = limit;
+    if let Some(ref region) = args.region {
+        params.region = Some(normalize_region(region)?);
     }
-    if search_type != "posts" {
-        params.search_type = search_type.to_string();
+    params.include_facets |= args.facets;
+    params.sr_detail |= args.sr_detail;
+
+    // Only the "new" sort is chronological, so only there does "before this id" mean newer
+    if args.since_id.is_some() && (params.sort != "new" || params.search_type != "posts") {
+        return Err(RdtError::Config(format!(
+            "--since-id needs a post search sorted by new (got sort '{}')",
+            params.sort
+        )));
+    }
+
+    if let Some(ref name) = args.save {
+        if name.trim().is_empty() {
+            return Err(RdtError::Config("--save needs a non-empty name".to_string()));
+        }
+        let mut saved = SavedSearches::load()?;
+        saved.insert(name.clone(), params.clone());
+        saved.save()?;
     }
 
     let client = RedditClient::new().await?;
-    let results = client.search(&params).await?;
 
-    format_output(&results, format)?;
-    Ok(())
+    if params.search_type == "subreddit" {
+        if args.cursor.after.is_some() || args.cursor.before.is_some() {
+            return Err(RdtError::Config(
+                "--after/--before only work when searching posts".to_string(),
+            ));
+        }
+        if args.interactive {
+            return Err(RdtError::Config(
+                "--interactive only works when searching posts".to_string(),
+            ));
+        }
+        warn_if_capped(params.limit);
+        let results = client.search_subreddits(&params.query, params.limit).await?;
+        if args.count_only {
+            let summary = serde_json::json!({ "query": results.query, "count": results.count });
+            return emit(&client, summary, args.with_meta, output);
+        }
+        return emit(&client, results, args.with_meta, output);
+    }
+
+    // Only a --before page is fetched in one request
+    if args.cursor.before.is_some() {
+        warn_if_capped(params.limit);
+    }
+    let mut results = if args.filter.is_empty() {
+        client.search_page(&params, &args.cursor).await?
+    } else {
+        client.search_filtered_page(&params, &args.filter, &args.cursor).await?
+    };
+    if let Some(ref since_id) = args.since_id {
+        results.keep_newer_than(since_id.trim_start_matches("t3_"));
+    }
+
+    if args.interactive {
+        // Only the choice goes to stdout, so `open "$(rdt search ... --interactive)"` works
+        if let Some(post) = choose_post(&results.posts)? {
+            if args.json {
+                format_output(post, output)?;
+            } else {
+                println!("{}", post.url);
+            }
+        }
+        return Ok(());
+    }
+
+    if args.count_only {
+        // Count of fetched results, bounded by limit - Reddit doesn't expose a total
+        let summary = serde_json::json!({
+            "query": results.query,
+            "count": results.count,
+        });
+        return emit(&client, summary, args.with_meta, output);
+    }
+
+    emit(&client, results, args.with_meta, output)
+}
+
+/// Print `data`, or with `--with-meta` wrap it as `{data, meta}` with the rate-limit
+/// headers of the last request
+fn emit<T: Serialize>(
+    client: &RedditClient,
+    data: T,
+    with_meta: bool,
+    output: &OutputOptions,
+) -> Result<()> {
+    if with_meta {
+        format_output(&ApiResponse::from_client(data, client), output)
+    } else {
+        format_output(&data, output)
+    }
+}
+
+/// Saved searches as `{name, ...params}`, sorted by name
+pub fn list_saved(output: &OutputOptions) -> Result<()> {
+    let saved = SavedSearches::load()?;
+    let mut list = Vec::new();
+    for (name, params) in saved.iter() {
+        let mut entry = serde_json::Map::new();
+        entry.insert("name".to_string(), name.clone().into());
+        if let serde_json::Value::Object(params) = serde_json::to_value(params)? {
+            entry.extend(params);
+        }
+        list.push(entry);
+    }
+    format_output(&list, output)
+}
+
+pub fn delete_saved(name: &str, output: &OutputOptions) -> Result<()> {
+    let mut saved = SavedSearches::load()?;
+    if !saved.remove(name) {
+        return Err(RdtError::Config(format!("No saved search named '{}'", name)));
+    }
+    saved.save()?;
+    format_output(&serde_json::json!({ "deleted": name }), output)
+}
+
+/// Numbered list and prompt on stderr; `None` if there's nothing to pick or the input is empty
+fn choose_post(posts: &[PostSummary]) -> Result<Option<&PostSummary>> {
+    if posts.is_empty() {
+        eprintln!("No results");
+        return Ok(None);
+    }
+
+    let mut stderr = std::io::stderr();
+    for (i, post) in posts.iter().enumerate() {
+        writeln!(
+            stderr,
+            "{:>3}. [{}] r/{} - {} ({} comments)",
+            i + 1,
+            post.score,
+            post.subreddit,
+            post.title,
+            post.num_comments
+        )?;
+    }
+    write!(stderr, "Select 1-{}: ", posts.len())?;
+    stderr.flush()?;
+
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    match line.parse::<usize>() {
+        Ok(n) if (1..=posts.len()).contains(&n) => Ok(Some(&posts[n - 1])),
+        _ => Err(RdtError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid selection '{}': expected 1-{}", line, posts.len()),
+        ))),
+    }
+}
+
+/// Validate an ISO 3166-1 alpha-2 country code (e.g. "us", "GB") for `geo_filter`
+fn normalize_region(region: &str) -> Result<String> {
+    if region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(region.to_ascii_uppercase())
+    } else {
+        Err(RdtError::Config(format!(
+            "Invalid region '{}': expected a 2-letter country code like US or GB",
+            region
+        )))
+    }
 }
@@ -1,19 +1,205 @@
-use crate::api::client::RedditClient;
+use crate::api::client::{normalize_listing_subreddit, RedditClient};
+use crate::api::models::{dedupe_crossposts, PageCursor, PostSummary};
+use crate::cli::warn_if_capped;
 use crate::error::Result;
-use crate::output::format_output;
+use crate::output::{format_output, OutputOptions};
+use crate::state::{cursor_key, CursorStore};
+use crate::stats;
+use std::collections::HashMap;
 
-pub async fn info(name: &str, format: &str) -> Result<()> {
+pub async fn info(name: &str, output: &OutputOptions) -> Result<()> {
     let client = RedditClient::new().await?;
     let info = client.get_subreddit_info(name).await?;
 
-    format_output(&info, format)?;
+    format_output(&info, output)?;
     Ok(())
 }
 
-pub async fn posts(name: &str, sort: &str, time: &str, limit: u32, format: &str) -> Result<()> {
+/// The `subreddit posts` command's flags, gathered by main.rs
+pub struct PostsArgs {
+    pub name: String,
+    pub sort: String,
+    pub time: String,
+    pub limit: u32,
+    pub deduplicate_crossposts: bool,
+    pub sr_detail: bool,
+    /// Only posts newer than this one, from the "new" listing
+    pub new_since: Option<String>,
+    pub fetch_selftext: bool,
+    /// `--continue`: resume from the saved cursor for this listing
+    pub resume: bool,
+    pub reset_cursor: bool,
+    pub cursor: PageCursor,
+}
+
+pub async fn posts(args: &PostsArgs, output: &OutputOptions) -> Result<()> {
+    let client = RedditClient::new().await?;
+
+    // Cursors are per listing; page size and output flags don't change where it stands
+    let name = normalize_listing_subreddit(&args.name)?;
+    let key = cursor_key(&["subreddit posts", &name, &args.sort, &args.time]);
+    if args.reset_cursor {
+        let mut store = CursorStore::load()?;
+        if store.remove(&key) {
+            store.save()?;
+        }
+    }
+    if args.resume {
+        let mut store = CursorStore::load()?;
+        let saved = PageCursor {
+            after: store.get(&key).map(str::to_string),
+            before: None,
+        };
+        warn_if_capped(args.limit);
+        let mut page = client
+            .get_subreddit_posts_page(
+                &name,
+                &args.sort,
+                &args.time,
+                args.limit,
+                args.sr_detail,
+                &saved,
+            )
+            .await?;
+        // At the end of the listing, stay after the last post so the next run is empty
+        // rather than starting over
+        let next = page.after.clone().or_else(|| page.posts.last().map(|p| p.fullname.clone()));
+        if let Some(cursor) = next {
+            store.set(key, cursor);
+            store.save()?;
+        }
+
+        if args.fetch_selftext {
+            fill_selftext(&client, &mut page.posts).await?;
+        }
+        if args.deduplicate_crossposts {
+            page.posts = dedupe_crossposts(page.posts);
+            page.count = page.posts.len();
+        }
+        format_output(&page, output)?;
+        return Ok(());
+    }
+
+    if let Some(ref since_id) = args.new_since {
+        let new = client.get_new_posts_since(&name, since_id, args.limit).await?;
+        let mut posts = new.posts;
+        if !new.reached_since {
+            eprintln!("{}", serde_json::json!({
+                "warning": format!(
+                    "{} wasn't reached (limit hit, or it's gone from /new); \
+                     posts between these and it may be missing",
+                    since_id
+                )
+            }));
+        }
+        if args.fetch_selftext {
+            fill_selftext(&client, &mut posts).await?;
+        }
+        // Taken before deduplication so the next poll starts from the true newest post
+        let newest_id = posts.first().map_or(since_id.clone(), |p| p.id.clone());
+        if args.deduplicate_crossposts {
+            posts = dedupe_crossposts(posts);
+        }
+        let result = serde_json::json!({
+            "posts": posts,
+            "count": posts.len(),
+            "newest_id": newest_id,
+            "reached_since": new.reached_since,
+        });
+        format_output(&result, output)?;
+        return Ok(());
+    }
+
+    // Only a --before page is fetched in one request
+    if args.cursor.before.is_some() {
+        warn_if_capped(args.limit);
+    }
+    let mut page = client
+        .get_subreddit_posts_from(
+            &name,
+            &args.sort,
+            &args.time,
+            args.limit,
+            args.sr_detail,
+            &args.cursor,
+        )
+        .await?;
+    if args.fetch_selftext {
+        fill_selftext(&client, &mut page.posts).await?;
+    }
+    if args.deduplicate_crossposts {
+        page.posts = dedupe_crossposts(page.posts);
+        page.count = page.posts.len();
+    }
+
+    format_output(&page, output)?;
+    Ok(())
+}
+
+/// Where to get the full body for a post whose listing selftext can't be trusted:
+/// the post itself when its text is empty or cut off, or the original post for a
+/// crosspost that came through without a body.
+fn selftext_source(post: &PostSummary) -> Option<&str> {
+    if post.is_self {
+        let truncated = post
+            .selftext
+            .as_deref()
+            .is_none_or(|text| text.ends_with("...") || text.ends_with('\u{2026}'));
+        truncated.then_some(post.id.as_str())
+    } else if post.selftext.is_none() {
+        post.crosspost_parent.as_deref()
+    } else {
+        None
+    }
+}
+
+/// Replace listing selftext with the full body from /by_id (100 posts per request)
+async fn fill_selftext(client: &RedditClient, posts: &mut [PostSummary]) -> Result<()> {
+    let mut ids: Vec<String> = Vec::new();
+    for post in posts.iter() {
+        if let Some(id) = selftext_source(post) {
+            let id = id.trim_start_matches("t3_").to_string();
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let full: HashMap<String, String> = client
+        .get_posts(&ids)
+        .await?
+        .into_iter()
+        .filter_map(|p| p.selftext.map(|text| (p.id, text)))
+        .collect();
+
+    for post in posts.iter_mut() {
+        let Some(id) = selftext_source(post).map(|id| id.trim_start_matches("t3_").to_string()) else {
+            continue;
+        };
+        if let Some(text) = full.get(&id) {
+            post.selftext = Some(text.clone());
+        }
+    }
+    Ok(())
+}
+
+pub async fn traffic(name: &str, output: &OutputOptions) -> Result<()> {
+    let client = RedditClient::new().await?;
+    let traffic = client.get_subreddit_traffic(name).await?;
+
+    format_output(&traffic, output)?;
+    Ok(())
+}
+
+pub async fn stats(name: &str, limit: u32, output: &OutputOptions) -> Result<()> {
+    let name = normalize_listing_subreddit(name)?;
     let client = RedditClient::new().await?;
-    let posts = client.get_subreddit_posts(name, sort, time, limit).await?;
+    let posts = client.get_subreddit_posts(&name, "new", "all", limit, false).await?;
 
-    format_output(&posts, format)?;
+    let stats = stats::compute(&name, &posts);
+    format_output(&stats, output)?;
     Ok(())
 }
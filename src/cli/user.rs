@@ -1,19 +1,85 @@
 use crate::api::client::RedditClient;
-use crate::error::Result;
-use crate::output::format_output;
+use crate::api::models::PageCursor;
+use crate::cli::warn_if_capped;
+use crate::error::{RdtError, Result};
+use crate::output::{format_output, OutputOptions};
 
-pub async fn info(username: &str, format: &str) -> Result<()> {
+pub async fn info(
+    username: &str,
+    moderated: bool,
+    trophies: bool,
+    output: &OutputOptions,
+) -> Result<()> {
     let client = RedditClient::new().await?;
     let info = client.get_user_info(username).await?;
 
-    format_output(&info, format)?;
+    if moderated || trophies {
+        let mut value = serde_json::to_value(&info)?;
+        if moderated {
+            value["moderated"] = serde_json::to_value(client.get_user_moderated(username).await?)?;
+        }
+        if trophies {
+            value["trophies"] = serde_json::to_value(client.get_user_trophies(username).await?)?;
+        }
+        format_output(&value, output)?;
+        return Ok(());
+    }
+
+    format_output(&info, output)?;
     Ok(())
 }
 
-pub async fn posts(username: &str, sort: &str, limit: u32, format: &str) -> Result<()> {
+const TIME_FILTERS: &[&str] = &["hour", "day", "week", "month", "year", "all"];
+
+pub async fn posts(
+    username: &str,
+    sort: &str,
+    time: Option<&str>,
+    limit: u32,
+    cursor: &PageCursor,
+    output: &OutputOptions,
+) -> Result<()> {
+    if let Some(time) = time {
+        if !matches!(sort, "top" | "controversial") {
+            return Err(RdtError::Config(format!(
+                "--time only applies to --sort top or controversial, not '{}'",
+                sort
+            )));
+        }
+        if !TIME_FILTERS.contains(&time) {
+            return Err(RdtError::Config(format!(
+                "Invalid --time '{}': use one of {}",
+                time,
+                TIME_FILTERS.join(", ")
+            )));
+        }
+    }
+
+    warn_if_capped(limit);
     let client = RedditClient::new().await?;
-    let posts = client.get_user_posts(username, sort, limit).await?;
+    let page = client.get_user_posts_page(username, sort, time, limit, cursor).await?;
+
+    format_output(&page, output)?;
+    Ok(())
+}
+
+pub async fn comments(
+    username: &str,
+    query: Option<&str>,
+    sort: &str,
+    limit: u32,
+    output: &OutputOptions,
+) -> Result<()> {
+    warn_if_capped(limit);
+    let client = RedditClient::new().await?;
+    let mut comments = client.get_user_comments(username, sort, limit).await?;
+
+    // Reddit has no server-side search over a user's comments, so filter here
+    if let Some(query) = query {
+        let needle = query.to_lowercase();
+        comments.retain(|c| c.body.to_lowercase().contains(&needle));
+    }
 
-    format_output(&posts, format)?;
+    format_output(&comments, output)?;
     Ok(())
 }
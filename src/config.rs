@@ -1,33 +1,189 @@
 use crate::error::{RdtError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+/// Active profile, set once from the `--profile` flag at startup
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Front-end for generated links, set from `[links] base` by the first `Config::load`
+static LINK_BASE: OnceLock<String> = OnceLock::new();
+
+const DEFAULT_LINK_BASE: &str = "https://reddit.com";
+
+/// Select a named profile for this process. Must be called before any `Config::load`.
+pub fn set_profile(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(RdtError::Config(format!(
+            "Invalid profile name '{}': use letters, digits, '-' or '_'",
+            name
+        )));
+    }
+    PROFILE
+        .set(name.to_string())
+        .map_err(|_| RdtError::Config("Profile already set".to_string()))
+}
+
+/// The active profile name, if one was selected
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+/// Base URL (no trailing slash) for post, comment and subreddit links in output
+pub fn link_base() -> &'static str {
+    LINK_BASE.get().map_or(DEFAULT_LINK_BASE, String::as_str)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub reddit: RedditConfig,
     #[serde(default)]
     pub aws: AwsConfig,
+    #[serde(default)]
+    pub ai: AiConfig,
+    #[serde(default)]
+    pub tui: TuiConfig,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default)]
+    pub links: LinksConfig,
     #[serde(skip)]
     config_dir: PathBuf,
+    /// `[reddit]` as read from disk, before env overrides (so `save` doesn't persist them)
+    #[serde(skip)]
+    file_reddit: RedditConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct RedditConfig {
     pub client_id: Option<String>,
     pub client_secret: Option<String>,
     pub access_token: Option<String>,
     pub refresh_token: Option<String>,
     pub user_agent: Option<String>,
+    /// Replaces https://oauth.reddit.com (env: RDT_API_BASE_URL)
+    pub api_base_url: Option<String>,
+    /// Replaces https://www.reddit.com (env: RDT_PUBLIC_BASE_URL)
+    pub public_base_url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+impl RedditConfig {
+    /// Fields that can be supplied through the environment, with their variable names
+    fn env_fields(&mut self) -> [(&'static str, &mut Option<String>); 5] {
+        [
+            ("RDT_CLIENT_ID", &mut self.client_id),
+            ("RDT_CLIENT_SECRET", &mut self.client_secret),
+            ("RDT_ACCESS_TOKEN", &mut self.access_token),
+            ("RDT_REFRESH_TOKEN", &mut self.refresh_token),
+            ("RDT_USER_AGENT", &mut self.user_agent),
+        ]
+    }
+}
+
+fn env_value(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|v| !v.is_empty())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AwsConfig {
     pub region: Option<String>,
     pub bedrock_model_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiConfig {
+    /// Queries with more words than this are routed to AI when no pattern matches
+    #[serde(default = "default_word_threshold")]
+    pub word_threshold: usize,
+}
+
+fn default_word_threshold() -> usize {
+    5
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            word_threshold: default_word_threshold(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TuiConfig {
+    /// Expand comment threads down to this depth when a post is opened (0 = all collapsed)
+    #[serde(default)]
+    pub auto_expand_depth: u32,
+    /// Start with an empty Home view instead of fetching r/all on launch
+    #[serde(default)]
+    pub skip_home_load: bool,
+    /// Show local date/time instead of relative age in the post detail view
+    #[serde(default)]
+    pub absolute_time: bool,
+    /// Show comment markdown as typed instead of styling links, bold and italics
+    #[serde(default)]
+    pub plain_markdown: bool,
+    /// Loading spinner: braille (default), dots, line, or none
+    #[serde(default)]
+    pub spinner: Option<String>,
+    /// Fetch comments in the background for the highlighted post, so opening it is instant
+    #[serde(default)]
+    pub prefetch_comments: bool,
+}
+
+/// Connection pool tuning; unset values keep reqwest's defaults
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HttpConfig {
+    pub pool_max_idle_per_host: Option<usize>,
+    pub pool_idle_timeout_secs: Option<u64>,
+    /// Sent with every Reddit request; can't replace User-Agent or Authorization
+    #[serde(default)]
+    pub extra_headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LinksConfig {
+    /// Front-end for generated links, e.g. https://old.reddit.com (default https://reddit.com)
+    pub base: Option<String>,
+}
+
+impl LinksConfig {
+    /// The configured base, checked to be a bare http(s) URL and without a trailing slash
+    fn validated_base(&self) -> Result<Option<String>> {
+        let Some(ref base) = self.base else {
+            return Ok(None);
+        };
+        let valid = url::Url::parse(base).is_ok_and(|u| {
+            matches!(u.scheme(), "http" | "https")
+                && u.host().is_some()
+                && u.query().is_none()
+                && u.fragment().is_none()
+        });
+        if !valid {
+            return Err(RdtError::Config(format!(
+                "links.base '{}' must be an http(s) URL like https://old.reddit.com",
+                base
+            )));
+        }
+        Ok(Some(base.trim_end_matches('/').to_string()))
+    }
+}
+
+fn unwritable(dir: &Path, err: std::io::Error) -> RdtError {
+    RdtError::Config(format!(
+        "Config directory {} is not writable ({}). Set RDT_CONFIG_DIR to a writable directory.",
+        dir.display(),
+        err
+    ))
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let config_dir = Self::config_dir()?;
@@ -40,17 +196,47 @@ impl Config {
             Config::default()
         };
 
+        // Credentials from the environment win over config.toml
+        config.file_reddit = config.reddit.clone();
+        for (var, field) in config.reddit.env_fields() {
+            if let Some(value) = env_value(var) {
+                *field = Some(value);
+            }
+        }
+
+        if let Some(base) = config.links.validated_base()? {
+            let _ = LINK_BASE.set(base);
+        }
+
         config.config_dir = config_dir;
         Ok(config)
     }
 
     pub fn save(&self) -> Result<()> {
         let config_dir = &self.config_dir;
-        fs::create_dir_all(config_dir)?;
+        fs::create_dir_all(config_dir).map_err(|e| unwritable(config_dir, e))?;
+
+        // Write back file values for fields still holding their env override, so
+        // secrets injected at runtime don't end up on disk
+        let mut reddit = self.reddit.clone();
+        let mut file_reddit = self.file_reddit.clone();
+        for ((var, field), (_, file_field)) in
+            reddit.env_fields().into_iter().zip(file_reddit.env_fields())
+        {
+            if env_value(var).is_some_and(|v| field.as_deref() == Some(v.as_str())) {
+                *field = file_field.take();
+            }
+        }
+
+        let on_disk = Config {
+            reddit,
+            ..self.clone()
+        };
 
         let config_path = config_dir.join("config.toml");
-        let content = toml::to_string_pretty(self).map_err(|e| RdtError::Config(e.to_string()))?;
-        fs::write(&config_path, content)?;
+        let content =
+            toml::to_string_pretty(&on_disk).map_err(|e| RdtError::Config(e.to_string()))?;
+        fs::write(&config_path, content).map_err(|e| unwritable(config_dir, e))?;
 
         // Set restrictive permissions on Unix
         #[cfg(unix)]
@@ -63,10 +249,40 @@ impl Config {
         Ok(())
     }
 
+    /// Fail early (before e.g. a browser login) if `save` would not be able to write
+    pub fn ensure_writable(&self) -> Result<()> {
+        let dir = &self.config_dir;
+        fs::create_dir_all(dir).map_err(|e| unwritable(dir, e))?;
+        let probe = dir.join(".write-test");
+        fs::write(&probe, b"").map_err(|e| unwritable(dir, e))?;
+        let _ = fs::remove_file(&probe);
+        Ok(())
+    }
+
+    /// `~/.config/rdt` (or `$RDT_CONFIG_DIR`), plus `profiles/<name>` when a profile is active
     pub fn config_dir() -> Result<PathBuf> {
-        dirs::config_dir()
-            .map(|p| p.join("rdt"))
-            .ok_or_else(|| RdtError::Config("Could not find config directory".to_string()))
+        let base = match std::env::var_os("RDT_CONFIG_DIR").filter(|v| !v.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::config_dir().map(|p| p.join("rdt")).ok_or_else(|| {
+                RdtError::Config(
+                    "Could not find config directory; set RDT_CONFIG_DIR".to_string(),
+                )
+            })?,
+        };
+
+        Ok(match profile() {
+            Some(name) => base.join("profiles").join(name),
+            None => base,
+        })
+    }
+
+    /// Default config that saves into `dir` (tests)
+    #[cfg(test)]
+    pub(crate) fn in_dir(dir: PathBuf) -> Self {
+        Self {
+            config_dir: dir,
+            ..Self::default()
+        }
     }
 
     pub fn config_path(&self) -> PathBuf {
@@ -29,14 +29,68 @@ pub enum RdtError {
     #[error("Pattern matching error: {0}")]
     Pattern(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Not authenticated. Run 'rdt auth login' first.")]
     NotAuthenticated,
 
-    #[error("Rate limited. Please wait before making more requests.")]
-    RateLimited,
+    #[error("Rate limited. {}", match reset_secs {
+        Some(secs) => format!("Retry in {}s.", secs),
+        None => "Please wait before making more requests.".to_string(),
+    })]
+    RateLimited {
+        /// Seconds until the limit resets, from `Retry-After`/`x-ratelimit-reset`
+        reset_secs: Option<u64>,
+    },
+
+    #[error("Subreddit r/{name} not found{}", did_you_mean(suggestions))]
+    SubredditNotFound {
+        name: String,
+        /// Similarly named subreddits, best match first
+        suggestions: Vec<String>,
+    },
+
+    /// `--deadline` passed before the command finished
+    #[error("Timed out after {secs}s (--deadline)")]
+    Timeout { secs: u64 },
 
     #[error("TUI error: {0}")]
     Tui(String),
+
+    /// Bad command line (unknown flag, missing argument, ...)
+    #[error("{0}")]
+    Usage(String),
+}
+
+impl RdtError {
+    /// Process exit code, so scripts can detect conditions without parsing stderr
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RdtError::NotAuthenticated => 2,
+            RdtError::Usage(_) => 64, // EX_USAGE
+            RdtError::Timeout { .. } => 124, // same as timeout(1)
+            _ => 1,
+        }
+    }
+
+    /// Variant name, used as the `type` in JSON errors
+    pub fn kind(&self) -> String {
+        let debug = format!("{:?}", self);
+        debug
+            .split(|c: char| !c.is_alphanumeric())
+            .next()
+            .unwrap_or("Unknown")
+            .to_string()
+    }
+}
+
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let names: Vec<String> = suggestions.iter().map(|n| format!("r/{}", n)).collect();
+    format!("; did you mean {}?", names.join(", "))
 }
 
 pub type Result<T> = std::result::Result<T, RdtError>;
@@ -0,0 +1,19 @@
+pub mod api;
+pub mod config;
+pub mod error;
+pub mod nlp;
+pub mod output;
+mod text;
+
+// Stable API: these re-exports keep their paths and signatures within a minor version.
+// Anything reached only through the module paths above may change with the CLI.
+pub use api::client::RedditClient;
+pub use api::models::{
+    CommentSummary, CommentThread, ImageSource, ModeratedSummary, NewPosts, OEmbed, PageCursor,
+    PostFilter, PostPage, PostSummary, SearchResults, SubredditSearchResults, SubredditSummary,
+    TrafficSummary, Trophy, UserSummary,
+};
+pub use config::Config;
+pub use error::{RdtError, Result};
+pub use nlp::router::{NlpRouter, SearchParams};
+pub use output::{render_output, write_output, OutputOptions};
@@ -1,22 +1,82 @@
-mod api;
 mod cli;
-mod config;
-mod error;
-mod nlp;
-mod output;
+mod state;
+mod stats;
 mod tui;
 
+use rdtcli::{api, config, error, nlp, output};
+use api::client::post_cursor;
+use api::models::PostFilter;
+use clap::error::ErrorKind;
 use clap::{Parser, Subcommand};
-use cli::{auth, post, search, subreddit, user};
+use std::io::IsTerminal;
+use cli::{auth, comment, post, search, subreddit, user};
+use output::OutputOptions;
 
 #[derive(Parser)]
 #[command(name = "rdt")]
 #[command(author, version, about = "Reddit CLI for AI agents", long_about = None)]
 struct Cli {
-    /// Output format (json, table)
+    /// Output format (json, ndjson, table)
     #[arg(short, long, default_value = "json", global = true)]
     format: String,
 
+    /// Print JSON on a single line instead of pretty-printed
+    #[arg(long, global = true)]
+    compact: bool,
+
+    /// Disable terminal escapes such as clickable links (also honors NO_COLOR)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Omit post selftext from output
+    #[arg(long, global = true)]
+    no_selftext: bool,
+
+    /// Truncate post selftext to at most N characters
+    #[arg(long, global = true, value_name = "N")]
+    selftext_limit: Option<usize>,
+
+    /// Collapse whitespace/line wraps and strip zero-width junk in post selftext
+    #[arg(long, global = true)]
+    flatten_selftext: bool,
+
+    /// Same cleanup as --flatten-selftext, for comment bodies
+    #[arg(long, global = true)]
+    flatten_comments: bool,
+
+    /// Columns for table output, in order (e.g. score,subreddit,author,comments,title)
+    #[arg(long, global = true, value_delimiter = ',')]
+    columns: Option<Vec<String>>,
+
+    /// Write output to this file instead of stdout
+    #[arg(long, global = true, value_name = "PATH")]
+    output_file: Option<std::path::PathBuf>,
+
+    /// Append to --output-file instead of overwriting (JSON is written as NDJSON)
+    #[arg(long, global = true, requires = "output_file")]
+    append: bool,
+
+    /// Print only the values at this path, one per line (e.g. posts[].title, [0].id)
+    #[arg(long, global = true, value_name = "PATH")]
+    select: Option<String>,
+
+    /// Print one line per record from a template, e.g. "{score} {subreddit} {title}"
+    /// (fields use --select paths; {{ and }} are literal braces)
+    #[arg(long, global = true, value_name = "TEMPLATE", conflicts_with = "select")]
+    output_template: Option<String>,
+
+    /// Report command-line mistakes as JSON on stderr (automatic when stdout isn't a terminal)
+    #[arg(long, global = true)]
+    json_errors: bool,
+
+    /// Give up after this many seconds, whatever the command is doing (exits with code 124)
+    #[arg(long, global = true, value_name = "SECS")]
+    deadline: Option<u64>,
+
+    /// Named profile for separate accounts (stored in ~/.config/rdt/profiles/<name>)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -32,13 +92,14 @@ enum Commands {
     /// Search Reddit
     Search {
         /// Search query (supports natural language)
-        query: String,
+        #[arg(required_unless_present_any = ["run", "list_saved", "delete_saved"])]
+        query: Option<String>,
 
         /// Limit to specific subreddit
         #[arg(short, long)]
         subreddit: Option<String>,
 
-        /// Search type: posts, comments
+        /// Search type: posts, comments, subreddit
         #[arg(short = 't', long, default_value = "posts")]
         r#type: String,
 
@@ -50,9 +111,87 @@ enum Commands {
         #[arg(long, default_value = "all")]
         time: String,
 
-        /// Maximum number of results
+        /// Maximum number of results (over 100 is fetched 100 per request, except with
+        /// --before)
         #[arg(short, long, default_value = "25")]
         limit: u32,
+
+        /// Localize results to a country (ISO 3166 code, e.g. US, GB)
+        #[arg(long)]
+        region: Option<String>,
+
+        /// Only keep posts with at least N comments (fetches extra pages to fill --limit)
+        #[arg(long, value_name = "N")]
+        min_comments: Option<u64>,
+
+        /// Include related subreddit suggestions (facets) in the results
+        #[arg(long)]
+        facets: bool,
+
+        /// Add subreddit subscribers/NSFW to each post (inline, no extra requests)
+        #[arg(long)]
+        sr_detail: bool,
+
+        /// Print why AI query parsing failed (to stderr) before falling back
+        #[arg(long)]
+        debug_ai: bool,
+
+        /// Print how the query is routed (patterns vs AI) to stderr
+        #[arg(long)]
+        explain: bool,
+
+        /// Report whether the query would be routed to AI, without searching
+        #[arg(long)]
+        would_use_ai: bool,
+
+        /// Pick a result from a numbered list (on stderr) and print its URL
+        #[arg(long, conflicts_with_all = ["count_only", "would_use_ai"])]
+        interactive: bool,
+
+        /// With --interactive, print the chosen post as JSON instead of its URL
+        #[arg(long, requires = "interactive")]
+        json: bool,
+
+        /// Only print the number of matches (Reddit has no true total, so this is capped by --limit)
+        #[arg(long)]
+        count_only: bool,
+
+        /// With --sort new, only results newer than this post; output includes `newest_id`
+        #[arg(long, value_name = "POST_ID", conflicts_with = "min_comments")]
+        since_id: Option<String>,
+
+        /// Also save the parsed search under NAME (in saved_searches.toml) for --run
+        #[arg(long, value_name = "NAME", conflicts_with = "would_use_ai")]
+        save: Option<String>,
+
+        /// Rerun a saved search instead of parsing a query (--sort/--time/--limit still override)
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["query", "subreddit", "explain", "would_use_ai", "save"]
+        )]
+        run: Option<String>,
+
+        /// Start after this post (id or t3_ fullname), e.g. the previous output's `after`
+        #[arg(long, value_name = "POST_ID", conflicts_with = "before")]
+        after: Option<String>,
+
+        /// Start before this post, e.g. the previous output's `before`
+        #[arg(long, value_name = "POST_ID")]
+        before: Option<String>,
+
+        /// Wrap the output as {data, meta}, with Reddit's rate-limit headers
+        /// (rate_limit_remaining, rate_limit_reset) in meta
+        #[arg(long, conflicts_with = "interactive")]
+        with_meta: bool,
+
+        /// List saved searches
+        #[arg(long, conflicts_with_all = ["query", "run", "delete_saved"])]
+        list_saved: bool,
+
+        /// Delete a saved search
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["query", "run"])]
+        delete_saved: Option<String>,
     },
 
     /// Post operations
@@ -61,20 +200,47 @@ enum Commands {
         action: PostAction,
     },
 
+    /// Comment operations
+    Comment {
+        #[command(subcommand)]
+        action: CommentAction,
+    },
+
     /// Subreddit operations
     Subreddit {
         #[command(subcommand)]
         action: SubredditAction,
     },
 
+    /// Configuration commands
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// User operations
     User {
         #[command(subcommand)]
         action: UserAction,
     },
 
+    /// Look up posts, comments and subreddits by fullname in one request
+    Info {
+        /// Fullnames such as t3_abc123 (post), t1_def456 (comment), t5_2qh1i (subreddit)
+        #[arg(required = true)]
+        fullnames: Vec<String>,
+    },
+
+    /// Check that Reddit is reachable (and the login works, if any)
+    Ping,
+
     /// Interactive TUI mode
-    Tui,
+    Tui {
+        /// Pop up the routing trace (pattern/AI decision, params, timing) after
+        /// each search; toggle it any time with D
+        #[arg(long)]
+        explain_routing: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -87,12 +253,32 @@ enum AuthAction {
     Logout,
 }
 
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Check the config is well-formed and has what the given operations need
+    Validate {
+        /// Operations the config must support: auth, ai (comma-separated)
+        #[arg(long = "for", value_name = "OPS", value_delimiter = ',')]
+        needs: Vec<String>,
+        /// Skip the access token check against Reddit
+        #[arg(long)]
+        offline: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum PostAction {
-    /// Get a post by ID
+    /// Get one or more posts by ID
     Get {
-        /// Post ID (e.g., "abc123" or full URL)
-        id: String,
+        /// Post ID(s) (e.g., "abc123" or full URL); several are fetched in one request
+        #[arg(required_unless_present = "stdin")]
+        ids: Vec<String>,
+        /// Include embedded media info (provider, author, thumbnail) for link posts
+        #[arg(long)]
+        oembed: bool,
+        /// Read ids/URLs from stdin (one per line) and stream the posts as NDJSON
+        #[arg(long, conflicts_with = "ids")]
+        stdin: bool,
     },
     /// Get comments for a post
     Comments {
@@ -104,6 +290,57 @@ enum PostAction {
         /// Maximum number of comments
         #[arg(short, long, default_value = "100")]
         limit: u32,
+        /// Include the full nested reply tree (default: top-level comments only)
+        #[arg(long)]
+        tree: bool,
+        /// With --tree, don't load replies under comments scoring below this
+        #[arg(long, value_name = "SCORE", allow_negative_numbers = true)]
+        prune_below: Option<i64>,
+        /// Fetch the whole thread (expanding "load more"), checkpointing to this file
+        /// so an interrupted run can be resumed. Outputs a flat list.
+        #[arg(long, value_name = "STATE_FILE", conflicts_with_all = ["tree", "prune_below"])]
+        resume: Option<std::path::PathBuf>,
+        /// Stop after this many comments across the whole tree (output gains a `truncated` flag)
+        #[arg(long, value_name = "N", conflicts_with = "resume")]
+        max_comments: Option<usize>,
+        /// Only comments by the post's author (e.g. AMA answers), as a flat list
+        #[arg(long, alias = "include-op-only")]
+        op_only: bool,
+        /// With --op-only, pair each answer with the comment it replied to
+        #[arg(long, requires = "op_only", conflicts_with = "resume")]
+        with_context: bool,
+        /// Order every level of the tree by creation time, oldest first
+        #[arg(long)]
+        oldest_first: bool,
+        /// Output `{post, comments}` with the post from the same request
+        #[arg(long, conflicts_with = "resume")]
+        with_post: bool,
+        /// Leave out comments Reddit shows collapsed (AutoMod, removed, low score)
+        /// and their replies
+        #[arg(long, conflicts_with = "resume")]
+        fold_collapsed: bool,
+    },
+    /// Create a text or link post (requires auth)
+    Submit {
+        /// Subreddit to post in
+        #[arg(short, long)]
+        subreddit: String,
+        /// Post title
+        #[arg(long)]
+        title: String,
+        /// Body for a text post
+        #[arg(long, required_unless_present = "url", conflicts_with = "url")]
+        text: Option<String>,
+        /// Link for a link post
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Upvote, downvote or clear your vote on a post or comment (requires auth)
+    Vote {
+        /// Post ID, URL or t3_ fullname, or a comment's t1_ fullname
+        id: String,
+        /// up, down or clear
+        direction: String,
     },
 }
 
@@ -124,9 +361,66 @@ enum SubredditAction {
         /// Time filter for top posts
         #[arg(long, default_value = "day")]
         time: String,
-        /// Maximum number of posts
+        /// Maximum number of posts (over 100 is fetched 100 per request, except with
+        /// --continue or --before)
         #[arg(short, long, default_value = "25")]
         limit: u32,
+        /// Collapse crossposts/reposts of the same link into one entry (see `also_in`)
+        #[arg(long)]
+        deduplicate_crossposts: bool,
+        /// Add subreddit subscribers/NSFW to each post (inline, no extra requests)
+        #[arg(long)]
+        sr_detail: bool,
+        /// Only posts newer than this one, from the "new" listing; output includes
+        /// `newest_id` to pass next time, and `reached_since` (false if the limit or page
+        /// cap cut the walk short, or the post is gone)
+        #[arg(long, value_name = "POST_ID", conflicts_with_all = ["sort", "time", "sr_detail"])]
+        new_since: Option<String>,
+        /// Re-fetch self posts whose body is empty or cut off in the listing (and
+        /// crossposts' original bodies) via batched /by_id requests
+        #[arg(long)]
+        fetch_selftext: bool,
+        /// Pick up after the last page a previous --continue run of this listing
+        /// (same subreddit, sort and time) returned, and remember where this one stops
+        #[arg(long = "continue", conflicts_with = "new_since")]
+        resume: bool,
+        /// Forget the saved --continue position for this listing
+        #[arg(long, conflicts_with = "new_since")]
+        reset_cursor: bool,
+        /// Start after this post (id or t3_ fullname), e.g. the previous output's `after`
+        #[arg(
+            long,
+            value_name = "POST_ID",
+            conflicts_with_all = ["before", "new_since", "resume"]
+        )]
+        after: Option<String>,
+        /// Start before this post, e.g. the previous output's `before`
+        #[arg(long, value_name = "POST_ID", conflicts_with_all = ["new_since", "resume"])]
+        before: Option<String>,
+    },
+    /// Aggregate stats over a subreddit's recent posts
+    Stats {
+        /// Subreddit name
+        name: String,
+        /// Number of recent posts to analyze (over 100 is fetched 100 per request)
+        #[arg(short, long, default_value = "100")]
+        limit: u32,
+    },
+    /// Unique visitors and pageviews by day/hour/month (requires auth; moderators only)
+    Traffic {
+        /// Subreddit name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommentAction {
+    /// Reply to a post or comment (requires auth)
+    Reply {
+        /// Post ID, URL or t3_ fullname, or a comment's t1_ fullname
+        parent: String,
+        /// Reply text (markdown)
+        text: String,
     },
 }
 
@@ -136,6 +430,12 @@ enum UserAction {
     Info {
         /// Username
         username: String,
+        /// Also list the subreddits the user moderates
+        #[arg(long)]
+        moderated: bool,
+        /// Also list the user's trophies
+        #[arg(long)]
+        trophies: bool,
     },
     /// Get user's posts
     Posts {
@@ -144,17 +444,120 @@ enum UserAction {
         /// Sort order: hot, new, top, controversial
         #[arg(long, default_value = "new")]
         sort: String,
-        /// Maximum number of posts
+        /// Time window for top/controversial: hour, day, week, month, year, all
+        #[arg(long)]
+        time: Option<String>,
+        /// Maximum number of posts (at most 100; Reddit's per-request cap)
         #[arg(short, long, default_value = "25")]
         limit: u32,
+        /// Start after this post (id or t3_ fullname), e.g. the previous output's `after`
+        #[arg(long, value_name = "POST_ID", conflicts_with = "before")]
+        after: Option<String>,
+        /// Start before this post, e.g. the previous output's `before`
+        #[arg(long, value_name = "POST_ID")]
+        before: Option<String>,
+    },
+    /// Get user's comments
+    Comments {
+        /// Username
+        username: String,
+        /// Only return comments containing this text (case-insensitive)
+        #[arg(short, long)]
+        query: Option<String>,
+        /// Sort order: hot, new, top, controversial
+        #[arg(long, default_value = "new")]
+        sort: String,
+        /// Maximum number of comments to fetch, before filtering (at most 100)
+        #[arg(short, long, default_value = "100")]
+        limit: u32,
     },
 }
 
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => exit_with_usage_error(e),
+    };
+
+    if let Some(ref profile) = cli.profile {
+        if let Err(e) = config::set_profile(profile) {
+            exit_with_error(e);
+        }
+    }
+
+    if let Err(e) = run(cli).await {
+        exit_with_error(e);
+    }
+}
 
-    let result = match cli.command {
+/// Agents get argument errors as JSON like any other error (with `--json-errors`, or
+/// whenever stdout isn't a terminal); people at a terminal get clap's usual output.
+/// `--help` and `--version` are never errors.
+fn exit_with_usage_error(e: clap::Error) -> ! {
+    let json = std::env::args_os().any(|arg| arg == "--json-errors")
+        || !std::io::stdout().is_terminal();
+    if !json || matches!(e.kind(), ErrorKind::DisplayHelp | ErrorKind::DisplayVersion) {
+        e.exit();
+    }
+    let message = if e.kind() == ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand {
+        "a subcommand is required; see --help".to_string()
+    } else {
+        // clap's message up to the usage section, on one line, without "error: "
+        let rendered = e.render().to_string();
+        let message = rendered.split("\n\n").next().unwrap_or_default();
+        let message = message.strip_prefix("error: ").unwrap_or(message);
+        message.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    exit_with_error(error::RdtError::Usage(message));
+}
+
+/// Print a JSON error to stderr and exit with the error's code
+fn exit_with_error(e: error::RdtError) -> ! {
+    let mut body = serde_json::json!({
+        "error": e.to_string(),
+        "type": e.kind(),
+    });
+    match e {
+        error::RdtError::RateLimited { reset_secs } => {
+            body["retry_after_secs"] = serde_json::json!(reset_secs);
+        }
+        error::RdtError::SubredditNotFound { ref suggestions, .. } => {
+            body["suggestions"] = serde_json::json!(suggestions);
+        }
+        _ => {}
+    }
+    eprintln!("{}", body);
+    std::process::exit(e.exit_code());
+}
+
+async fn run(cli: Cli) -> error::Result<()> {
+    let output = OutputOptions {
+        format: cli.format,
+        compact: cli.compact,
+        no_color: cli.no_color,
+        no_selftext: cli.no_selftext,
+        selftext_limit: cli.selftext_limit,
+        flatten_selftext: cli.flatten_selftext,
+        flatten_comments: cli.flatten_comments,
+        columns: cli.columns,
+        output_file: cli.output_file,
+        append: cli.append,
+        select: cli.select,
+        template: cli.output_template,
+    };
+
+    let command = dispatch(cli.command, output);
+    match cli.deadline {
+        Some(secs) => tokio::time::timeout(std::time::Duration::from_secs(secs), command)
+            .await
+            .unwrap_or(Err(error::RdtError::Timeout { secs })),
+        None => command.await,
+    }
+}
+
+async fn dispatch(command: Commands, output: OutputOptions) -> error::Result<()> {
+    match command {
         Commands::Auth { action } => match action {
             AuthAction::Login => auth::login().await,
             AuthAction::Status => auth::status().await,
@@ -167,41 +570,167 @@ async fn main() {
             sort,
             time,
             limit,
+            region,
+            min_comments,
+            facets,
+            sr_detail,
+            count_only,
+            debug_ai,
+            explain,
+            would_use_ai,
+            interactive,
+            json,
+            since_id,
+            save,
+            run,
+            after,
+            before,
+            with_meta,
+            list_saved,
+            delete_saved,
         } => {
-            search::search(&query, subreddit.as_deref(), &r#type, &sort, &time, limit, &cli.format)
-                .await
+            if list_saved {
+                return search::list_saved(&output);
+            }
+            if let Some(name) = delete_saved {
+                return search::delete_saved(&name, &output);
+            }
+            let args = search::SearchArgs {
+                query: query.unwrap_or_default(),
+                subreddit,
+                search_type: r#type,
+                sort,
+                time,
+                limit,
+                region,
+                filter: PostFilter { min_comments },
+                facets,
+                sr_detail,
+                count_only,
+                debug_ai,
+                explain,
+                would_use_ai,
+                interactive,
+                json,
+                since_id,
+                save,
+                run,
+                cursor: post_cursor(after.as_deref(), before.as_deref()),
+                with_meta,
+            };
+            search::search(&args, &output).await
         }
         Commands::Post { action } => match action {
-            PostAction::Get { id } => post::get(&id, &cli.format).await,
-            PostAction::Comments { id, sort, limit } => {
-                post::comments(&id, &sort, limit, &cli.format).await
+            PostAction::Get { stdin: true, oembed, .. } => post::get_stdin(oembed, &output).await,
+            PostAction::Get { ids, oembed, .. } => post::get(&ids, oembed, &output).await,
+            PostAction::Comments {
+                id,
+                sort,
+                limit,
+                tree,
+                prune_below,
+                resume,
+                max_comments,
+                op_only,
+                with_context,
+                oldest_first,
+                with_post,
+                fold_collapsed,
+            } => {
+                let args = post::CommentsArgs {
+                    id,
+                    sort,
+                    limit,
+                    tree,
+                    prune_below,
+                    resume,
+                    max_comments,
+                    op_only,
+                    with_context,
+                    oldest_first,
+                    with_post,
+                    fold_collapsed,
+                };
+                post::comments(&args, &output).await
             }
+            PostAction::Submit {
+                subreddit,
+                title,
+                text,
+                url,
+            } => post::submit(&subreddit, &title, text.as_deref(), url.as_deref(), &output).await,
+            PostAction::Vote { id, direction } => post::vote(&id, &direction, &output).await,
+        },
+        Commands::Comment { action } => match action {
+            CommentAction::Reply { parent, text } => comment::reply(&parent, &text, &output).await,
         },
         Commands::Subreddit { action } => match action {
-            SubredditAction::Info { name } => subreddit::info(&name, &cli.format).await,
+            SubredditAction::Info { name } => subreddit::info(&name, &output).await,
             SubredditAction::Posts {
                 name,
                 sort,
                 time,
                 limit,
-            } => subreddit::posts(&name, &sort, &time, limit, &cli.format).await,
+                deduplicate_crossposts,
+                sr_detail,
+                new_since,
+                fetch_selftext,
+                resume,
+                reset_cursor,
+                after,
+                before,
+            } => {
+                let args = subreddit::PostsArgs {
+                    name,
+                    sort,
+                    time,
+                    limit,
+                    deduplicate_crossposts,
+                    sr_detail,
+                    new_since,
+                    fetch_selftext,
+                    resume,
+                    reset_cursor,
+                    cursor: post_cursor(after.as_deref(), before.as_deref()),
+                };
+                subreddit::posts(&args, &output).await
+            }
+            SubredditAction::Stats { name, limit } => {
+                subreddit::stats(&name, limit, &output).await
+            }
+            SubredditAction::Traffic { name } => subreddit::traffic(&name, &output).await,
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::Validate { needs, offline } => {
+                cli::config::validate(&needs, offline, &output).await
+            }
         },
         Commands::User { action } => match action {
-            UserAction::Info { username } => user::info(&username, &cli.format).await,
+            UserAction::Info {
+                username,
+                moderated,
+                trophies,
+            } => user::info(&username, moderated, trophies, &output).await,
             UserAction::Posts {
                 username,
                 sort,
+                time,
                 limit,
-            } => user::posts(&username, &sort, limit, &cli.format).await,
+                after,
+                before,
+            } => {
+                let cursor = post_cursor(after.as_deref(), before.as_deref());
+                user::posts(&username, &sort, time.as_deref(), limit, &cursor, &output).await
+            }
+            UserAction::Comments {
+                username,
+                query,
+                sort,
+                limit,
+            } => user::comments(&username, query.as_deref(), &sort, limit, &output).await,
         },
-        Commands::Tui => tui::run().await,
-    };
-
-    if let Err(e) = result {
-        eprintln!("{}", serde_json::json!({
-            "error": e.to_string(),
-            "type": format!("{:?}", e).split('(').next().unwrap_or("Unknown")
-        }));
-        std::process::exit(1);
+        Commands::Info { fullnames } => cli::info::info(&fullnames, &output).await,
+        Commands::Ping => cli::ping::ping(&output).await,
+        Commands::Tui { explain_routing } => tui::run(explain_routing).await,
     }
 }
@@ -1,3 +1,4 @@
+use crate::api::client::normalize_subreddit;
 use crate::nlp::router::SearchParams;
 use regex::Regex;
 
@@ -17,12 +18,27 @@ impl PatternMatcher {
         // IMPORTANT: More specific patterns must come BEFORE simpler ones!
         // e.g., "top <query> from this week" must come before "top <query>"
         let patterns = vec![
+            // === Subreddit discovery (before anything matching "in <subreddit>") ===
+
+            // "find subreddits about <query>" / "show me subreddits for <query>"
+            Pattern {
+                regex: Regex::new(
+                    r"(?i)^(?:find|show)(?:\s+me)?\s+subreddits?\s+(?:about|for|on)\s+(.+)$",
+                )
+                .unwrap(),
+                extractor: Box::new(|caps| SearchParams {
+                    query: caps[1].trim().to_string(),
+                    search_type: "subreddit".to_string(),
+                    ..Default::default()
+                }),
+            },
+
             // === Most specific patterns first (4 components) ===
 
             // "top <query> in <subreddit> from this week"
             Pattern {
                 regex: Regex::new(
-                    r"(?i)^top\s+(.+?)\s+in\s+r?/?(\w+)\s+from\s+this\s+week$",
+                    r"(?i)^top\s+(.+?)\s+in\s+([/\w]+)\s+from\s+this\s+week$",
                 )
                 .unwrap(),
                 extractor: Box::new(|caps| SearchParams {
@@ -36,7 +52,7 @@ impl PatternMatcher {
             // "recent <query> in <subreddit> from this week"
             Pattern {
                 regex: Regex::new(
-                    r"(?i)^recent\s+(.+?)\s+in\s+r?/?(\w+)\s+from\s+this\s+week$",
+                    r"(?i)^recent\s+(.+?)\s+in\s+([/\w]+)\s+from\s+this\s+week$",
                 )
                 .unwrap(),
                 extractor: Box::new(|caps| SearchParams {
@@ -52,7 +68,7 @@ impl PatternMatcher {
 
             // "top <query> in <subreddit>"
             Pattern {
-                regex: Regex::new(r"(?i)^top\s+(.+?)\s+in\s+r?/?(\w+)$").unwrap(),
+                regex: Regex::new(r"(?i)^top\s+(.+?)\s+in\s+([/\w]+)$").unwrap(),
                 extractor: Box::new(|caps| SearchParams {
                     query: caps[1].trim().to_string(),
                     subreddit: Some(caps[2].to_string()),
@@ -82,7 +98,7 @@ impl PatternMatcher {
             },
             // "recent <query> in <subreddit>"
             Pattern {
-                regex: Regex::new(r"(?i)^recent\s+(.+?)\s+in\s+r?/?(\w+)$").unwrap(),
+                regex: Regex::new(r"(?i)^recent\s+(.+?)\s+in\s+([/\w]+)$").unwrap(),
                 extractor: Box::new(|caps| SearchParams {
                     query: caps[1].trim().to_string(),
                     subreddit: Some(caps[2].to_string()),
@@ -92,7 +108,7 @@ impl PatternMatcher {
             },
             // "<query> in <subreddit> from this week"
             Pattern {
-                regex: Regex::new(r"(?i)^(.+?)\s+in\s+r?/?(\w+)\s+from\s+this\s+week$").unwrap(),
+                regex: Regex::new(r"(?i)^(.+?)\s+in\s+([/\w]+)\s+from\s+this\s+week$").unwrap(),
                 extractor: Box::new(|caps| SearchParams {
                     query: caps[1].trim().to_string(),
                     subreddit: Some(caps[2].to_string()),
@@ -102,7 +118,7 @@ impl PatternMatcher {
             },
             // "posts about <query> in <subreddit>"
             Pattern {
-                regex: Regex::new(r"(?i)^posts?\s+about\s+(.+?)\s+in\s+r?/?(\w+)$").unwrap(),
+                regex: Regex::new(r"(?i)^posts?\s+about\s+(.+?)\s+in\s+([/\w]+)$").unwrap(),
                 extractor: Box::new(|caps| SearchParams {
                     query: caps[1].trim().to_string(),
                     subreddit: Some(caps[2].to_string()),
@@ -132,7 +148,7 @@ impl PatternMatcher {
             },
             // "<query> in <subreddit>"
             Pattern {
-                regex: Regex::new(r"(?i)^(.+?)\s+in\s+r?/?(\w+)$").unwrap(),
+                regex: Regex::new(r"(?i)^(.+?)\s+in\s+([/\w]+)$").unwrap(),
                 extractor: Box::new(|caps| SearchParams {
                     query: caps[1].trim().to_string(),
                     subreddit: Some(caps[2].to_string()),
@@ -199,10 +215,27 @@ impl PatternMatcher {
     }
 
     /// Try to match the query against all patterns
+    /// Patterns whose captured subreddit is not a valid name are skipped
     pub fn try_match(&self, query: &str) -> Option<SearchParams> {
+        self.find(query).map(|(_, params)| params)
+    }
+
+    /// The regex of the pattern `try_match` would use for this query
+    pub fn matching_pattern(&self, query: &str) -> Option<&str> {
+        self.find(query).map(|(pattern, _)| pattern.regex.as_str())
+    }
+
+    fn find(&self, query: &str) -> Option<(&Pattern, SearchParams)> {
         for pattern in &self.patterns {
             if let Some(caps) = pattern.regex.captures(query) {
-                return Some((pattern.extractor)(&caps));
+                let mut params = (pattern.extractor)(&caps);
+                if let Some(ref sub) = params.subreddit {
+                    match normalize_subreddit(sub) {
+                        Ok(name) => params.subreddit = Some(name),
+                        Err(_) => continue,
+                    }
+                }
+                return Some((pattern, params));
             }
         }
         None
@@ -235,6 +268,14 @@ mod tests {
         assert_eq!(result.sort, "top");
     }
 
+    #[test]
+    fn test_matching_pattern() {
+        let matcher = PatternMatcher::new();
+        let pattern = matcher.matching_pattern("top rust tutorials").unwrap();
+        assert!(pattern.starts_with("(?i)^top"));
+        assert_eq!(matcher.matching_pattern("why is the sky blue"), None);
+    }
+
     #[test]
     fn test_from_this_week() {
         let matcher = PatternMatcher::new();
@@ -243,6 +284,37 @@ mod tests {
         assert_eq!(result.time, "week");
     }
 
+    #[test]
+    fn test_find_subreddits() {
+        let matcher = PatternMatcher::new();
+        let result = matcher.try_match("find subreddits about rust in embedded").unwrap();
+        assert_eq!(result.query, "rust in embedded");
+        assert_eq!(result.search_type, "subreddit");
+        assert_eq!(result.subreddit, None);
+    }
+
+    #[test]
+    fn test_subreddit_normalized() {
+        let matcher = PatternMatcher::new();
+        let result = matcher.try_match("async in r/Rust").unwrap();
+        assert_eq!(result.subreddit, Some("rust".to_string()));
+
+        let result = matcher.try_match("tokio in /r/rust").unwrap();
+        assert_eq!(result.subreddit, Some("rust".to_string()));
+
+        // A bare name starting with "r" must not lose its first letter
+        let result = matcher.try_match("async in rust").unwrap();
+        assert_eq!(result.subreddit, Some("rust".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_subreddit() {
+        assert_eq!(normalize_subreddit("r/AskReddit").unwrap(), "askreddit");
+        assert_eq!(normalize_subreddit("u_spez").unwrap(), "u_spez");
+        assert!(normalize_subreddit("r/a").is_err());
+        assert!(normalize_subreddit("not-valid").is_err());
+    }
+
     #[test]
     fn test_complex_pattern() {
         let matcher = PatternMatcher::new();
@@ -1,4 +1,5 @@
-use crate::config::Config;
+use crate::api::client::normalize_subreddit;
+use crate::config::{AiConfig, Config};
 use crate::error::{RdtError, Result};
 use crate::nlp::patterns::PatternMatcher;
 use regex::Regex;
@@ -20,7 +21,17 @@ pub struct SearchParams {
     pub sort: String,
     pub time: String,
     pub limit: u32,
+    /// "posts" (default), "comments", or "subreddit" to search for communities
     pub search_type: String,
+    /// ISO 3166 country code for Reddit's `geo_filter`
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Ask Reddit for related subreddit facets
+    #[serde(default)]
+    pub include_facets: bool,
+    /// Ask Reddit to inline subreddit details (subscribers, NSFW) on each post
+    #[serde(default)]
+    pub sr_detail: bool,
     #[serde(skip)]
     pub parse_method: Option<ParseMethod>,
 }
@@ -34,15 +45,28 @@ impl Default for SearchParams {
             time: "all".to_string(),
             limit: 25,
             search_type: "posts".to_string(),
+            region: None,
+            include_facets: false,
+            sr_detail: false,
             parse_method: None,
         }
     }
 }
 
+/// Why `needs_nlp` did or didn't route a query to AI
+#[derive(Debug, Clone, Serialize)]
+pub struct NlpDecision {
+    pub needs_ai: bool,
+    pub reason: String,
+    pub word_threshold: usize,
+}
+
 /// Router that decides between pattern matching and AI
 pub struct NlpRouter {
     pattern_matcher: PatternMatcher,
     needs_ai_patterns: Vec<Regex>,
+    word_threshold: usize,
+    debug_ai: bool,
 }
 
 impl NlpRouter {
@@ -62,33 +86,69 @@ impl NlpRouter {
             Regex::new(r"(?i)\b(compare|versus|vs\.?|difference between)\b").unwrap(),
         ];
 
+        let word_threshold = Config::load()
+            .map(|c| c.ai.word_threshold)
+            .unwrap_or_else(|_| AiConfig::default().word_threshold);
+
         Self {
             pattern_matcher: PatternMatcher::new(),
             needs_ai_patterns,
+            word_threshold,
+            debug_ai: false,
         }
     }
 
+    /// Report why the AI layer failed (on stderr) instead of silently falling back
+    pub fn with_debug_ai(mut self, enabled: bool) -> Self {
+        self.debug_ai = enabled;
+        self
+    }
+
+    /// Whether one of the built-in patterns handles the query (no AI call)
+    pub fn pattern_matches(&self, query: &str) -> bool {
+        self.pattern_matcher.try_match(query).is_some()
+    }
+
+    /// The regex of the built-in pattern that handles the query, if any
+    pub fn matching_pattern(&self, query: &str) -> Option<&str> {
+        self.pattern_matcher.matching_pattern(query)
+    }
+
     /// Check if the query needs NLP/AI processing
     pub fn needs_nlp(&self, query: &str) -> bool {
+        self.explain_needs_nlp(query).needs_ai
+    }
+
+    /// Same as `needs_nlp`, but also reports which rule decided
+    pub fn explain_needs_nlp(&self, query: &str) -> NlpDecision {
+        let decision = |needs_ai: bool, reason: String| NlpDecision {
+            needs_ai,
+            reason,
+            word_threshold: self.word_threshold,
+        };
+
         // First, try pattern matching - if it matches, no need for AI
         if self.pattern_matcher.try_match(query).is_some() {
-            return false;
+            return decision(false, "matched a built-in pattern".to_string());
         }
 
         // Check if query matches any "needs AI" patterns
         for pattern in &self.needs_ai_patterns {
             if pattern.is_match(query) {
-                return true;
+                return decision(true, format!("matched heuristic {}", pattern.as_str()));
             }
         }
 
         // Check for multi-word natural language that doesn't match simple patterns
-        let words: Vec<&str> = query.split_whitespace().collect();
-        if words.len() > 5 {
-            return true;
+        let words = query.split_whitespace().count();
+        if words > self.word_threshold {
+            return decision(
+                true,
+                format!("{} words exceeds threshold of {}", words, self.word_threshold),
+            );
         }
 
-        false
+        decision(false, "simple query".to_string())
     }
 
     /// Parse query using pattern matching first, then AI fallback
@@ -106,11 +166,19 @@ impl NlpRouter {
                 params.parse_method = Some(ParseMethod::AI);
                 Ok(params)
             }
-            Err(_) => Ok(SearchParams {
-                query: query.to_string(),
-                parse_method: Some(ParseMethod::Fallback),
-                ..Default::default()
-            }),
+            Err(e) => {
+                if self.debug_ai {
+                    eprintln!("{}", serde_json::json!({
+                        "warning": "AI query parsing failed, falling back to raw query",
+                        "error": e.to_string(),
+                    }));
+                }
+                Ok(SearchParams {
+                    query: query.to_string(),
+                    parse_method: Some(ParseMethod::Fallback),
+                    ..Default::default()
+                })
+            }
         }
     }
 
@@ -144,6 +212,7 @@ Return JSON with these fields:
 - sort: one of "relevance", "hot", "new", "top" (default: "relevance")
 - time: one of "hour", "day", "week", "month", "year", "all" (default: "all")
 - limit: number of results 1-100 (default: 25)
+- search_type: "subreddit" if the user is looking for communities rather than posts, else "posts"
 
 Example input: "what are the best rust tutorials from this week"
 Example output: {{"query": "rust tutorials", "sort": "top", "time": "week", "limit": 25}}
@@ -209,14 +278,22 @@ Now parse the query and return only the JSON:"#,
                 .as_str()
                 .unwrap_or(query)
                 .to_string(),
-            subreddit: parsed["subreddit"].as_str().map(String::from),
+            subreddit: parsed["subreddit"]
+                .as_str()
+                .and_then(|s| normalize_subreddit(s).ok()),
             sort: parsed["sort"]
                 .as_str()
                 .unwrap_or("relevance")
                 .to_string(),
             time: parsed["time"].as_str().unwrap_or("all").to_string(),
             limit: parsed["limit"].as_u64().unwrap_or(25) as u32,
-            search_type: "posts".to_string(),
+            search_type: match parsed["search_type"].as_str() {
+                Some("subreddit") => "subreddit".to_string(),
+                _ => "posts".to_string(),
+            },
+            region: None,
+            include_facets: false,
+            sr_detail: false,
             parse_method: None, // Set by caller
         })
     }
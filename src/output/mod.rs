@@ -1,25 +1,396 @@
-use crate::error::Result;
+mod table;
+
+use crate::error::{RdtError, Result};
 use serde::Serialize;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+
+/// Output settings from global CLI flags
+#[derive(Debug, Clone, Default)]
+pub struct OutputOptions {
+    pub format: String,
+    pub compact: bool,
+    pub no_color: bool,
+    pub no_selftext: bool,
+    pub selftext_limit: Option<usize>,
+    pub flatten_selftext: bool,
+    pub flatten_comments: bool,
+    /// Table columns to show, in order (None = the default set)
+    pub columns: Option<Vec<String>>,
+    /// Write here instead of stdout
+    pub output_file: Option<PathBuf>,
+    /// Append to `output_file` (JSON becomes NDJSON) instead of overwriting it
+    pub append: bool,
+    /// Print only the values at this path (e.g. `posts[].title`), one per line
+    pub select: Option<String>,
+    /// Print this template once per record, e.g. `{score} {title}`
+    pub template: Option<String>,
+}
+
+impl OutputOptions {
+    /// Clickable links only make sense on an interactive terminal that allows escapes
+    pub fn hyperlinks_enabled(&self) -> bool {
+        !self.no_color
+            && self.output_file.is_none()
+            && std::env::var_os("NO_COLOR").is_none()
+            && std::io::stdout().is_terminal()
+    }
+}
+
+/// Wrap `text` in an OSC 8 escape so supporting terminals render it as a link to `url`
+pub fn hyperlink(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
+
+/// Format output and print it to stdout, or to `options.output_file`
+pub fn format_output<T: Serialize>(data: &T, options: &OutputOptions) -> Result<()> {
+    let output = render_output(data, options)?;
+
+    // Nothing to add; a blank line would be an invalid NDJSON record
+    if options.output_file.is_some() && options.append && output.is_empty() {
+        return Ok(());
+    }
+    writeln!(open_output(options)?, "{}", output)?;
+    Ok(())
+}
+
+/// Where output goes: `options.output_file` (appending or truncating per
+/// `options.append`), or stdout. Public so the binary's streaming commands share it.
+pub fn open_output(options: &OutputOptions) -> Result<Box<dyn Write>> {
+    match options.output_file {
+        Some(ref path) => Ok(Box::new(
+            std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(options.append)
+                .truncate(!options.append)
+                .open(path)?,
+        )),
+        None => Ok(Box::new(std::io::stdout().lock())),
+    }
+}
+
+/// Format output into any writer (ignores `options.output_file`)
+pub fn write_output<T: Serialize, W: Write>(
+    writer: &mut W,
+    data: &T,
+    options: &OutputOptions,
+) -> Result<()> {
+    writeln!(writer, "{}", render_output(data, options)?)?;
+    Ok(())
+}
+
+/// The formatted text for `data`, without a trailing newline
+pub fn render_output<T: Serialize>(data: &T, options: &OutputOptions) -> Result<String> {
+    let mut value = serde_json::to_value(data)?;
+    apply_selftext_options(&mut value, options);
+
+    if options.columns.is_some() && options.format != "table" {
+        return Err(RdtError::Config(
+            "--columns only applies to --format table".to_string(),
+        ));
+    }
+
+    if options.template.is_some() && options.format == "table" {
+        return Err(RdtError::Config(
+            "--output-template can't be combined with --format table".to_string(),
+        ));
+    }
+    if let Some(ref template) = options.template {
+        return render_template(&value, template);
+    }
+
+    match options.select {
+        Some(_) if options.format == "table" => Err(RdtError::Config(
+            "--select can't be combined with --format table".to_string(),
+        )),
+        Some(ref path) => select_lines(&value, path),
+        None => match options.format.as_str() {
+            "table" => table::render(&value, options),
+            "ndjson" => to_ndjson_with_meta(&value),
+            // A JSON document can't be appended to, so appends are one record per line
+            _ if options.append => to_ndjson(&value),
+            _ => to_json(&value, options.compact),
+        },
+    }
+}
+
+/// Rows held by a wrapper object like SearchResults, under "posts", "subreddits" or "comments"
+fn wrapped_rows(map: &serde_json::Map<String, serde_json::Value>) -> Option<&Vec<serde_json::Value>> {
+    rows_key(map).and_then(|key| map[key].as_array())
+}
+
+/// Which of "posts", "subreddits" or "comments" holds a wrapper object's rows
+fn rows_key(map: &serde_json::Map<String, serde_json::Value>) -> Option<&'static str> {
+    ["posts", "subreddits", "comments"]
+        .into_iter()
+        .find(|key| map.get(*key).is_some_and(|v| v.is_array()))
+}
+
+/// The records in `value`: list items (including a result's `posts`, `subreddits`
+/// or `comments`), else the value itself
+fn records(value: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match value {
+        serde_json::Value::Array(items) => items.iter().collect(),
+        serde_json::Value::Object(map) => match wrapped_rows(map) {
+            Some(rows) => rows.iter().collect(),
+            None => vec![value],
+        },
+        _ => vec![value],
+    }
+}
+
+/// One compact JSON record per line
+fn to_ndjson(value: &serde_json::Value) -> Result<String> {
+    let lines = records(value)
+        .into_iter()
+        .map(serde_json::to_string)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(lines.join("\n"))
+}
+
+/// `--format ndjson`: one record per line like `to_ndjson`, led by a `{"meta": {...}}`
+/// line holding the rest of a wrapper object (query, count, after, ...)
+fn to_ndjson_with_meta(value: &serde_json::Value) -> Result<String> {
+    let records = to_ndjson(value)?;
+    let meta = match value {
+        serde_json::Value::Object(map) => match rows_key(map) {
+            Some(rows) => map
+                .iter()
+                .filter(|(k, _)| k.as_str() != rows)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<serde_json::Map<_, _>>(),
+            None => return Ok(records),
+        },
+        _ => return Ok(records),
+    };
+    let meta = serde_json::to_string(&serde_json::json!({ "meta": meta }))?;
+    if records.is_empty() {
+        return Ok(meta);
+    }
+    Ok(format!("{}\n{}", meta, records))
+}
+
+/// One step of a `--select` path
+#[derive(Debug, PartialEq)]
+enum PathStep {
+    Field(String),
+    Index(usize),
+    /// `[]`: every element of an array
+    Each,
+}
+
+/// Parse a dot/bracket path like `posts[].title`, `[0].id` or `.subreddits[2]`
+fn parse_path(path: &str) -> Result<Vec<PathStep>> {
+    let invalid = || RdtError::Config(format!("Invalid --select path '{}'", path));
+    let mut steps = Vec::new();
+
+    // `.` (or nothing) selects the whole value, as in jq
+    let path = path.strip_prefix('.').unwrap_or(path);
+    if path.is_empty() {
+        return Ok(steps);
+    }
+    for segment in path.split('.') {
+        let (field, mut brackets) = match segment.find('[') {
+            Some(i) => segment.split_at(i),
+            None => (segment, ""),
+        };
+        if !field.is_empty() {
+            steps.push(PathStep::Field(field.to_string()));
+        } else if brackets.is_empty() {
+            return Err(invalid());
+        }
+        while !brackets.is_empty() {
+            let end = brackets.find(']').ok_or_else(invalid)?;
+            let inner = &brackets[1..end];
+            steps.push(if inner.is_empty() {
+                PathStep::Each
+            } else {
+                PathStep::Index(inner.parse().map_err(|_| invalid())?)
+            });
+            brackets = &brackets[end + 1..];
+            if !brackets.is_empty() && !brackets.starts_with('[') {
+                return Err(invalid());
+            }
+        }
+    }
+    Ok(steps)
+}
+
+/// Every value reached by following `steps`; missing fields/indexes yield nothing
+fn select_values<'a>(value: &'a serde_json::Value, steps: &[PathStep]) -> Vec<&'a serde_json::Value> {
+    let Some((step, rest)) = steps.split_first() else {
+        return vec![value];
+    };
+    match step {
+        PathStep::Field(name) => value
+            .get(name.as_str())
+            .map_or_else(Vec::new, |v| select_values(v, rest)),
+        PathStep::Index(i) => value
+            .get(*i)
+            .map_or_else(Vec::new, |v| select_values(v, rest)),
+        PathStep::Each => value
+            .as_array()
+            .map(|items| items.iter().flat_map(|v| select_values(v, rest)).collect())
+            .unwrap_or_default(),
+    }
+}
 
-/// Format and print output based on the format type
-pub fn format_output<T: Serialize>(data: &T, format: &str) -> Result<()> {
-    match format {
-        "json" => {
-            let output = serde_json::to_string_pretty(data)?;
-            println!("{}", output);
+/// `--select` output: one match per line, strings unquoted and anything else as compact JSON
+fn select_lines(value: &serde_json::Value, path: &str) -> Result<String> {
+    let lines = select_values(value, &parse_path(path)?)
+        .into_iter()
+        .map(|v| match v {
+            serde_json::Value::String(s) => Ok(s.clone()),
+            other => serde_json::to_string(other),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(lines.join("\n"))
+}
+
+/// A piece of an `--output-template`
+#[derive(Debug, PartialEq)]
+enum TemplatePart {
+    Literal(String),
+    /// `{path}`, using `--select` path syntax
+    Field(Vec<PathStep>),
+}
+
+/// Split a template like `{score} {title}` into literals and placeholders.
+/// `{{` and `}}` are literal braces.
+fn parse_template(template: &str) -> Result<Vec<TemplatePart>> {
+    let invalid = |why: &str| {
+        RdtError::Config(format!("Invalid --output-template '{}': {}", template, why))
+    };
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut path = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => path.push(c),
+                        None => return Err(invalid("unclosed '{'")),
+                    }
+                }
+                let path = path.trim();
+                if path.is_empty() {
+                    return Err(invalid("empty '{}'"));
+                }
+                if !literal.is_empty() {
+                    parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(TemplatePart::Field(parse_path(path)?));
+            }
+            '}' => return Err(invalid("unmatched '}' (use '}}' for a literal brace)")),
+            c => literal.push(c),
         }
-        "table" => {
-            // For now, fall back to JSON for table format
-            // TODO: Implement proper table formatting
-            let output = serde_json::to_string_pretty(data)?;
-            println!("{}", output);
+    }
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    Ok(parts)
+}
+
+/// `--output-template` output: one filled-in line per record. Missing fields and
+/// nulls are empty, strings unquoted, other values compact JSON (comma-joined for `[]`).
+fn render_template(value: &serde_json::Value, template: &str) -> Result<String> {
+    let parts = parse_template(template)?;
+    let lines = records(value)
+        .into_iter()
+        .map(|record| {
+            let mut line = String::new();
+            for part in &parts {
+                match part {
+                    TemplatePart::Literal(text) => line.push_str(text),
+                    TemplatePart::Field(steps) => {
+                        let values = select_values(record, steps)
+                            .into_iter()
+                            .filter(|v| !v.is_null())
+                            .map(|v| match v {
+                                serde_json::Value::String(s) => Ok(s.clone()),
+                                other => serde_json::to_string(other),
+                            })
+                            .collect::<std::result::Result<Vec<_>, _>>()?;
+                        line.push_str(&values.join(","));
+                    }
+                }
+            }
+            Ok(line)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(lines.join("\n"))
+}
+
+fn to_json(value: &serde_json::Value, compact: bool) -> Result<String> {
+    if compact {
+        Ok(serde_json::to_string(value)?)
+    } else {
+        Ok(serde_json::to_string_pretty(value)?)
+    }
+}
+
+/// Drop, flatten or truncate `selftext` fields (and flatten comment `body` fields)
+/// anywhere in the output
+fn apply_selftext_options(value: &mut serde_json::Value, options: &OutputOptions) {
+    if !options.no_selftext
+        && options.selftext_limit.is_none()
+        && !options.flatten_selftext
+        && !options.flatten_comments
+    {
+        return;
+    }
+
+    match value {
+        serde_json::Value::Object(map) => {
+            if options.no_selftext {
+                map.remove("selftext");
+            } else if let Some(serde_json::Value::String(text)) = map.get_mut("selftext") {
+                if options.flatten_selftext {
+                    *text = crate::text::flatten(text);
+                }
+                if let Some(limit) = options.selftext_limit {
+                    if text.chars().count() > limit {
+                        *text = format!("{}...", text.chars().take(limit).collect::<String>());
+                    }
+                }
+            }
+            if options.flatten_comments {
+                if let Some(serde_json::Value::String(body)) = map.get_mut("body") {
+                    *body = crate::text::flatten(body);
+                }
+            }
+            for child in map.values_mut() {
+                apply_selftext_options(child, options);
+            }
         }
-        _ => {
-            let output = serde_json::to_string_pretty(data)?;
-            println!("{}", output);
+        serde_json::Value::Array(items) => {
+            for item in items {
+                apply_selftext_options(item, options);
+            }
         }
+        _ => {}
+    }
+}
+
+/// Print a debug note to stderr when `RDT_DEBUG` is set.
+/// Kept off by default so stdout stays clean JSON and the TUI isn't disturbed.
+pub(crate) fn debug_note(message: &str) {
+    if std::env::var_os("RDT_DEBUG").is_some() {
+        eprintln!("{}", serde_json::json!({ "debug": message }));
     }
-    Ok(())
 }
 
 /// Wrapper for consistent API response format
@@ -46,9 +417,116 @@ impl<T: Serialize> ApiResponse<T> {
         }
     }
 
+    /// Wrap `data`, with the rate-limit state of the client's latest response if it had one
+    pub fn from_client(data: T, client: &crate::api::client::RedditClient) -> Self {
+        match client.rate_limit() {
+            Some(limit) => Self::new(data).with_rate_limit(limit.remaining, limit.reset_secs),
+            None => Self::new(data),
+        }
+    }
+
     pub fn with_rate_limit(mut self, remaining: u32, reset: u64) -> Self {
         self.meta.rate_limit_remaining = Some(remaining);
         self.meta.rate_limit_reset = Some(reset);
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_paths() {
+        let value = json!({
+            "posts": [
+                { "id": "a", "title": "First", "flair": null, "images": ["x", "y"] },
+                { "id": "b", "title": "Second", "score": 3 },
+            ],
+            "count": 2,
+        });
+
+        assert_eq!(select_lines(&value, "posts[].title").unwrap(), "First\nSecond");
+        assert_eq!(select_lines(&value, ".posts[1].score").unwrap(), "3");
+        assert_eq!(select_lines(&value, "count").unwrap(), "2");
+        assert_eq!(select_lines(&json!("hi"), ".").unwrap(), "hi");
+        // Missing fields are skipped, explicit nulls kept
+        assert_eq!(select_lines(&value, "posts[].flair").unwrap(), "null");
+        assert_eq!(select_lines(&value, "posts[].images[]").unwrap(), "x\ny");
+        assert_eq!(select_lines(&value, "posts[0]").unwrap(), r#"{"id":"a","title":"First","flair":null,"images":["x","y"]}"#);
+        assert_eq!(select_lines(&json!([{ "id": "z" }]), "[].id").unwrap(), "z");
+
+        for bad in ["posts[", "posts[x]", "posts..title", "posts[]x"] {
+            assert!(parse_path(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_output_template() {
+        let value = json!({
+            "posts": [
+                { "score": 42, "subreddit": "rust", "title": "Hello", "flair": null, "images": ["x", "y"] },
+                { "score": -1, "subreddit": "golang", "title": "World" },
+            ],
+            "count": 2,
+        });
+
+        assert_eq!(
+            render_template(&value, "{score} r/{subreddit} {title}").unwrap(),
+            "42 r/rust Hello\n-1 r/golang World"
+        );
+        // Missing and null fields are empty; [] values are comma-joined
+        assert_eq!(
+            render_template(&value, "[{flair}] {missing}|{images[]}").unwrap(),
+            "[] |x,y\n[] |"
+        );
+        assert_eq!(render_template(&value, "{{{title}}}").unwrap(), "{Hello}\n{World}");
+        // A single object is one record
+        assert_eq!(render_template(&json!({ "name": "spez" }), "u/{name}").unwrap(), "u/spez");
+
+        for bad in ["{title", "title}", "{}", "{posts[x]}"] {
+            assert!(parse_template(bad).is_err(), "{}", bad);
+        }
+    }
+
+    fn test_post(id: &str) -> crate::api::models::PostSummary {
+        let post: crate::api::models::Post = serde_json::from_value(json!({ "id": id })).unwrap();
+        post.into()
+    }
+
+    #[test]
+    fn test_ndjson_format() {
+        let options = OutputOptions {
+            format: "ndjson".to_string(),
+            ..Default::default()
+        };
+        let results = crate::api::models::SearchResults {
+            query: "rust".to_string(),
+            subreddit: None,
+            sort: "relevance".to_string(),
+            posts: vec![test_post("a"), test_post("b")],
+            count: 2,
+            after: Some("t3_b".to_string()),
+            before: None,
+            related_subreddits: None,
+            newest_id: None,
+        };
+        let out = render_output(&results, &options).unwrap();
+        let lines: Vec<serde_json::Value> =
+            out.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], json!({
+                "meta": {
+                    "query": "rust", "subreddit": null, "sort": "relevance", "count": 2,
+                    "after": "t3_b", "before": null,
+                }
+            }));
+        assert_eq!(lines[1]["id"], "a");
+        assert_eq!(lines[2]["id"], "b");
+
+        let out = render_output(&test_post("c"), &options).unwrap();
+        assert_eq!(out.lines().count(), 1);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&out).unwrap()["id"], "c");
+    }
+}
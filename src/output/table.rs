@@ -0,0 +1,239 @@
+use crate::error::{RdtError, Result};
+use crate::output::{hyperlink, OutputOptions};
+use serde_json::Value;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Widest cell (in terminal columns) before it gets cut off with "..."
+const MAX_CELL_WIDTH: usize = 80;
+
+const POST_COLUMNS: &[&str] = &["score", "num_comments", "subreddit", "title"];
+const COMMENT_COLUMNS: &[&str] = &["score", "author", "body"];
+
+// Everything `--columns` may pick from, per row kind
+const POST_FIELDS: &[&str] = &[
+    "id", "score", "num_comments", "subreddit", "author", "title", "url", "created_utc",
+    "post_hint",
+];
+const COMMENT_FIELDS: &[&str] = &[
+    "id", "score", "author", "body", "depth", "reply_count", "created_utc", "link_id",
+];
+
+/// Friendlier names accepted by `--columns`
+fn column_alias(name: &str) -> &str {
+    match name {
+        "comments" => "num_comments",
+        "replies" => "reply_count",
+        "created" => "created_utc",
+        other => other,
+    }
+}
+
+/// Render output as a plain-text table
+pub fn render(value: &Value, options: &OutputOptions) -> Result<String> {
+    let rows: Vec<&Value> = match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(map) => match super::wrapped_rows(map) {
+            Some(rows) => rows.iter().collect(),
+            None => return Ok(render_record(value)),
+        },
+        _ => return Ok(cell_text(value)),
+    };
+
+    let Some(first) = rows.first() else {
+        return Ok(String::new());
+    };
+
+    let (defaults, available): (Vec<&str>, Vec<&str>) = if first.get("title").is_some() {
+        (POST_COLUMNS.to_vec(), POST_FIELDS.to_vec())
+    } else if first.get("body").is_some() {
+        (COMMENT_COLUMNS.to_vec(), COMMENT_FIELDS.to_vec())
+    } else {
+        (scalar_keys(first), scalar_keys(first))
+    };
+
+    let columns = match &options.columns {
+        Some(requested) => select_columns(requested, &available)?,
+        None => defaults,
+    };
+
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| columns.iter().map(|c| cell_text(&row[*c])).collect())
+        .collect();
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            cells
+                .iter()
+                .map(|row| row[i].width())
+                .chain(std::iter::once(column.len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let links = options.hyperlinks_enabled();
+    let title_column = columns.iter().position(|c| *c == "title");
+    let mut lines = vec![pad_row(
+        &columns.iter().map(|c| c.to_uppercase()).collect::<Vec<_>>(),
+        &widths,
+    )];
+
+    for (row, cell_row) in rows.iter().zip(&cells) {
+        let mut line = String::new();
+        for (i, text) in cell_row.iter().enumerate() {
+            // Titles link to the post; padding uses the plain text so the escape can't skew it
+            match row["url"].as_str() {
+                Some(url) if links && Some(i) == title_column => {
+                    line.push_str(&hyperlink(url, text))
+                }
+                _ => line.push_str(text),
+            }
+            if i + 1 < widths.len() {
+                let padding = widths[i].saturating_sub(text.width());
+                line.push_str(&" ".repeat(padding + 2));
+            }
+        }
+        lines.push(line);
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Resolve `--columns` names (and aliases) against what this kind of row has
+fn select_columns<'a>(requested: &[String], available: &[&'a str]) -> Result<Vec<&'a str>> {
+    requested
+        .iter()
+        .map(|name| {
+            let name = column_alias(name.trim());
+            available.iter().copied().find(|c| *c == name).ok_or_else(|| {
+                RdtError::Config(format!(
+                    "Unknown column '{}'. Available: {}",
+                    name,
+                    available.join(", ")
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Single object: one `key: value` line per scalar field
+fn render_record(value: &Value) -> String {
+    scalar_keys(value)
+        .into_iter()
+        .map(|key| format!("{}: {}", key, cell_text(&value[key])))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn scalar_keys(value: &Value) -> Vec<&str> {
+    value
+        .as_object()
+        .map(|map| {
+            map.iter()
+                .filter(|(_, v)| !v.is_array() && !v.is_object())
+                .map(|(k, _)| k.as_str())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Pad every cell in `cells` to its column width (in terminal columns, so wide CJK and
+/// emoji characters line up); the last column (if any) is left unpadded
+fn pad_row(cells: &[String], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (i, cell) in cells.iter().enumerate() {
+        line.push_str(cell);
+        if i + 1 < widths.len() {
+            let padding = widths[i].saturating_sub(cell.width());
+            line.push_str(&" ".repeat(padding + 2));
+        }
+    }
+    line
+}
+
+fn cell_text(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.replace('\n', " "),
+        other => other.to_string(),
+    };
+    if text.width() <= MAX_CELL_WIDTH {
+        return text;
+    }
+    let mut used = 0;
+    let kept: String = text
+        .chars()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= MAX_CELL_WIDTH
+        })
+        .collect();
+    format!("{}...", kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_columns() {
+        let posts = json!([
+            { "score": 3, "num_comments": 5, "subreddit": "rust", "author": "ferris", "title": "Hi", "url": "u" },
+        ]);
+        let options = OutputOptions {
+            no_color: true,
+            columns: Some(vec!["author".into(), "comments".into(), "title".into()]),
+            ..Default::default()
+        };
+        let output = render(&posts, &options).unwrap();
+        assert_eq!(output.lines().nth(1), Some("ferris  5             Hi"));
+
+        let options = OutputOptions {
+            columns: Some(vec!["karma".into()]),
+            ..options
+        };
+        assert!(render(&posts, &options).is_err());
+    }
+
+    #[test]
+    fn test_wide_characters_align() {
+        let posts = json!([
+            { "score": 1, "num_comments": 2, "subreddit": "日本", "title": "こんにちは" },
+            { "score": 3, "num_comments": 4, "subreddit": "rust", "title": "🦀 crab" },
+        ]);
+        let options = OutputOptions {
+            no_color: true,
+            ..Default::default()
+        };
+        let output = render(&posts, &options).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        // "日本" is four columns wide, like "rust"
+        assert_eq!(lines[1], "1      2             日本       こんにちは");
+        assert_eq!(lines[2], "3      4             rust       🦀 crab");
+
+        let long = cell_text(&json!("漢".repeat(50)));
+        assert_eq!(long.width(), MAX_CELL_WIDTH + 3);
+    }
+
+    #[test]
+    fn test_render_posts() {
+        let posts = json!([
+            { "score": 1200, "num_comments": 5, "subreddit": "rust", "title": "Hello", "url": "https://reddit.com/a" },
+            { "score": 7, "num_comments": 130, "subreddit": "programming", "title": "World", "url": "https://reddit.com/b" },
+        ]);
+        let options = OutputOptions {
+            no_color: true,
+            ..Default::default()
+        };
+
+        let output = render(&posts, &options).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "SCORE  NUM_COMMENTS  SUBREDDIT    TITLE");
+        assert_eq!(lines[1], "1200   5             rust         Hello");
+        assert_eq!(lines[2], "7      130           programming  World");
+    }
+}
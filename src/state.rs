@@ -0,0 +1,157 @@
+use crate::config::Config;
+use crate::error::{RdtError, Result};
+use crate::nlp::router::SearchParams;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Saved `after` cursors for `--continue`, one per command invocation
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CursorStore {
+    cursors: BTreeMap<String, String>,
+}
+
+impl CursorStore {
+    fn path() -> Result<PathBuf> {
+        Ok(Config::config_dir()?.join("cursors.json"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            RdtError::Config(format!("Invalid cursor file {}: {}", path.display(), e))
+        })
+    }
+
+    /// Write atomically so an interrupted save keeps the previous cursors
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.cursors.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: String, cursor: String) {
+        self.cursors.insert(key, cursor);
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.cursors.remove(key).is_some()
+    }
+}
+
+/// Named searches from `search --save`, kept as parsed params so `--run` skips the NLP step
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SavedSearches {
+    searches: BTreeMap<String, SearchParams>,
+}
+
+impl SavedSearches {
+    fn path() -> Result<PathBuf> {
+        Ok(Config::config_dir()?.join("saved_searches.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        toml::from_str(&content).map_err(|e| {
+            RdtError::Config(format!("Invalid saved searches file {}: {}", path.display(), e))
+        })
+    }
+
+    /// Write atomically, like `CursorStore::save`
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content =
+            toml::to_string_pretty(self).map_err(|e| RdtError::Config(e.to_string()))?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SearchParams> {
+        self.searches.get(name)
+    }
+
+    pub fn insert(&mut self, name: String, params: SearchParams) {
+        self.searches.insert(name, params);
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.searches.remove(name).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SearchParams)> {
+        self.searches.iter()
+    }
+}
+
+/// Key for a command and the arguments that pick its listing. FNV-1a, so keys stay
+/// the same across builds (std's hasher makes no such promise).
+pub fn cursor_key(parts: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in parts.join("\0").bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_key_depends_on_every_part() {
+        let key = cursor_key(&["subreddit posts", "rust", "hot", "day"]);
+        assert_eq!(key, cursor_key(&["subreddit posts", "rust", "hot", "day"]));
+        assert_ne!(key, cursor_key(&["subreddit posts", "rust", "new", "day"]));
+        // Parts are separated, so shifting text between them changes the key
+        assert_ne!(cursor_key(&["ab", "c"]), cursor_key(&["a", "bc"]));
+        assert_eq!(key.len(), 16);
+    }
+
+    #[test]
+    fn test_saved_searches_toml_round_trip() {
+        let mut saved = SavedSearches::default();
+        saved.insert(
+            "weekly-rust".to_string(),
+            SearchParams {
+                query: "rust".to_string(),
+                subreddit: Some("rust".to_string()),
+                time: "week".to_string(),
+                ..Default::default()
+            },
+        );
+        saved.insert("plain".to_string(), SearchParams::default());
+
+        let content = toml::to_string_pretty(&saved).unwrap();
+        assert!(content.contains("[weekly-rust]"), "{}", content);
+        let loaded: SavedSearches = toml::from_str(&content).unwrap();
+        let params = loaded.get("weekly-rust").unwrap();
+        assert_eq!(params.subreddit.as_deref(), Some("rust"));
+        assert_eq!(params.time, "week");
+        assert_eq!(loaded.get("plain").unwrap().subreddit, None);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::api::models::PostSummary;
+use chrono::{DateTime, Timelike};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// How many authors to include in `top_authors`
+const TOP_AUTHORS: usize = 10;
+
+/// Aggregate stats over a sample of subreddit posts
+#[derive(Debug, Serialize)]
+pub struct SubredditStats {
+    pub subreddit: String,
+    pub post_count: usize,
+    pub average_score: f64,
+    pub median_comments: f64,
+    pub post_types: PostTypeBreakdown,
+    pub top_authors: Vec<AuthorCount>,
+    /// Posts per hour of day (UTC), index 0 = 00:00-00:59
+    pub hour_histogram: [u32; 24],
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct PostTypeBreakdown {
+    pub self_posts: usize,
+    pub image: usize,
+    pub link: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthorCount {
+    pub author: String,
+    pub posts: usize,
+}
+
+/// Compute aggregates for a list of posts
+pub fn compute(subreddit: &str, posts: &[PostSummary]) -> SubredditStats {
+    let post_count = posts.len();
+
+    let average_score = if post_count > 0 {
+        posts.iter().map(|p| p.score as f64).sum::<f64>() / post_count as f64
+    } else {
+        0.0
+    };
+
+    let mut comments: Vec<u64> = posts.iter().map(|p| p.num_comments).collect();
+    comments.sort_unstable();
+    let median_comments = match comments.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (comments[n / 2 - 1] + comments[n / 2]) as f64 / 2.0,
+        n => comments[n / 2] as f64,
+    };
+
+    let mut post_types = PostTypeBreakdown::default();
+    let mut authors: HashMap<&str, usize> = HashMap::new();
+    let mut hour_histogram = [0u32; 24];
+
+    for post in posts {
+        if post.is_self {
+            post_types.self_posts += 1;
+        } else if post.post_hint.as_deref() == Some("image") {
+            post_types.image += 1;
+        } else {
+            post_types.link += 1;
+        }
+
+        *authors.entry(post.author.as_str()).or_default() += 1;
+
+        let time = post
+            .created_utc
+            .and_then(|ts| DateTime::from_timestamp(ts as i64, 0));
+        if let Some(time) = time {
+            hour_histogram[time.hour() as usize] += 1;
+        }
+    }
+
+    let mut top_authors: Vec<AuthorCount> = authors
+        .into_iter()
+        .map(|(author, posts)| AuthorCount {
+            author: author.to_string(),
+            posts,
+        })
+        .collect();
+    // Most posts first, ties broken by name so output is stable
+    top_authors.sort_by(|a, b| b.posts.cmp(&a.posts).then_with(|| a.author.cmp(&b.author)));
+    top_authors.truncate(TOP_AUTHORS);
+
+    SubredditStats {
+        subreddit: subreddit.to_string(),
+        post_count,
+        average_score,
+        median_comments,
+        post_types,
+        top_authors,
+        hour_histogram,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(
+        author: &str,
+        score: i64,
+        num_comments: u64,
+        is_self: bool,
+        hint: Option<&str>,
+    ) -> PostSummary {
+        PostSummary {
+            id: "x".to_string(),
+            fullname: "t3_x".to_string(),
+            title: String::new(),
+            author: author.to_string(),
+            subreddit: "rust".to_string(),
+            url: String::new(),
+            score,
+            upvote_ratio: 1.0,
+            num_comments,
+            created_utc: Some(3600.0 * 5.0), // 05:00 UTC
+            is_self,
+            nsfw: false,
+            spoiler: false,
+            stickied: false,
+            locked: false,
+            flair: None,
+            awards: 0,
+            post_hint: hint.map(String::from),
+            thumbnail: None,
+            image_url: None,
+            images: Vec::new(),
+            thumbnails: Vec::new(),
+            selftext: None,
+            crosspost_parent: None,
+            subreddit_subscribers: None,
+            subreddit_nsfw: None,
+            also_in: Vec::new(),
+            link_url: None,
+            oembed: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let posts = vec![
+            post("alice", 10, 1, true, None),
+            post("bob", 20, 5, false, Some("image")),
+            post("alice", 30, 9, false, Some("link")),
+            post("carol", 40, 3, false, None),
+        ];
+        let stats = compute("rust", &posts);
+
+        assert_eq!(stats.post_count, 4);
+        assert_eq!(stats.average_score, 25.0);
+        assert_eq!(stats.median_comments, 4.0);
+        assert_eq!(stats.post_types.self_posts, 1);
+        assert_eq!(stats.post_types.image, 1);
+        assert_eq!(stats.post_types.link, 2);
+        assert_eq!(stats.top_authors[0].author, "alice");
+        assert_eq!(stats.top_authors[0].posts, 2);
+        assert_eq!(stats.hour_histogram[5], 4);
+    }
+}
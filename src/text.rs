@@ -0,0 +1,23 @@
+/// Zero-width characters Reddit's editor leaves behind (often as `&#x200B;` spacer paragraphs)
+const ZERO_WIDTH: &[char] = &['\u{200B}', '\u{200C}', '\u{200D}', '\u{FEFF}'];
+
+/// Collapse all whitespace runs (including hard line wraps) into single spaces and strip
+/// zero-width characters, both literal and HTML-escaped
+pub fn flatten(text: &str) -> String {
+    let text = text
+        .replace("&amp;#x200B;", "")
+        .replace("&#x200B;", "")
+        .replace(ZERO_WIDTH, "");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatten() {
+        let body = "First line\nwrapped here.\n\n&#x200B;\n\nSecond\u{200B}   paragraph\t ";
+        assert_eq!(flatten(body), "First line wrapped here. Second paragraph");
+    }
+}
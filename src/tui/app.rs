@@ -1,5 +1,6 @@
 use crate::api::client::RedditClient;
-use crate::api::models::{CommentSummary, PostSummary, SearchResults};
+use crate::api::models::{CommentSummary, PostSummary, SearchResults, SubredditSummary};
+use crate::config::Config;
 use crate::error::Result;
 use crate::nlp::router::NlpRouter;
 use crate::tui::ui;
@@ -8,13 +9,22 @@ use ratatui::prelude::*;
 use ratatui_image::picker::Picker;
 use ratatui_image::protocol::StatefulProtocol;
 use std::cell::RefCell;
-use std::time::Duration;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// How long a post must stay highlighted before its comments are prefetched
+const PREFETCH_DELAY: Duration = Duration::from_millis(400);
+
+/// Most prefetched comment threads kept around
+const PREFETCH_CACHE_SIZE: usize = 10;
 
 /// Current view/screen in the TUI
 #[derive(Debug, Clone, PartialEq)]
 pub enum View {
     Home,
     SearchResults,
+    Subreddit,
     PostDetail,
 }
 
@@ -25,6 +35,12 @@ pub enum InputMode {
     Editing,
 }
 
+/// A background comment fetch for the highlighted post
+struct Prefetch {
+    post_id: String,
+    task: JoinHandle<Result<Vec<CommentSummary>>>,
+}
+
 /// Main application state
 pub struct App {
     pub running: bool,
@@ -33,13 +49,16 @@ pub struct App {
 
     // Search state
     pub search_input: String,
-    pub cursor_position: usize,
+    pub cursor_position: usize, // byte offset into search_input, on a char boundary
     pub search_sort: String,
     pub search_time: String,
 
     // Data
     pub home_posts: Vec<PostSummary>,
+    pub home_deferred: bool, // r/all not fetched yet (tui.skip_home_load)
     pub search_results: Option<SearchResults>,
+    pub subreddit_posts: Vec<PostSummary>,
+    pub current_subreddit: Option<SubredditSummary>,
     pub selected_post_index: usize,
     pub current_post: Option<PostSummary>,
     pub comments: Vec<CommentSummary>,
@@ -49,9 +68,13 @@ pub struct App {
     pub loading: bool,
     pub loading_message: String,
     pub error_message: Option<String>,
+    pub status_message: Option<String>,
 
     // Debug info
     pub debug_info: Option<String>,
+    pub routing_trace: Option<serde_json::Value>, // how the last search was routed
+    pub show_routing: bool,                         // routing trace popup (D)
+    pub explain_routing: bool,                      // open the popup after every search
 
     // Scroll state for post detail
     pub scroll_offset: u16,
@@ -59,12 +82,30 @@ pub struct App {
     // Image support
     pub image_picker: Option<Picker>,
     pub current_image: RefCell<Option<StatefulProtocol>>,
+    pub current_image_url: Option<String>,
+    pub gallery_index: usize, // position in current_post.images
+    pub show_meta: bool,      // expanded metadata panel in PostDetail
+    pub revealed: HashSet<String>, // NSFW/spoiler post ids the user chose to show
+
+    // Comment prefetching (tui.prefetch_comments)
+    prefetch_enabled: bool,
+    prefetch: Option<Prefetch>,
+    highlighted: Option<(String, Instant)>, // post id and when it was highlighted
+    comment_cache: Vec<(String, Vec<CommentSummary>)>, // oldest first
+    wide: bool, // terminal is wide enough for the list + preview split
+
+    // Settings from config
+    pub auto_expand_depth: u32,
+    pub absolute_time: bool,
+    pub plain_markdown: bool,
+    pub spinner: Option<String>,
 }
 
 impl App {
     pub fn new() -> Self {
         // Try to detect terminal image capabilities
         let image_picker = Picker::from_query_stdio().ok();
+        let config = Config::load().unwrap_or_default();
 
         Self {
             running: true,
@@ -75,18 +116,38 @@ impl App {
             search_sort: "relevance".to_string(),
             search_time: "all".to_string(),
             home_posts: Vec::new(),
+            home_deferred: config.tui.skip_home_load,
             search_results: None,
+            subreddit_posts: Vec::new(),
+            current_subreddit: None,
             selected_post_index: 0,
             current_post: None,
             comments: Vec::new(),
             selected_comment_index: 0,
-            loading: true, // Start loading
+            loading: !config.tui.skip_home_load, // Start loading unless home is deferred
             loading_message: "Loading...".to_string(),
             error_message: None,
+            status_message: None,
             debug_info: None,
+            routing_trace: None,
+            show_routing: false,
+            explain_routing: false,
             scroll_offset: 0,
             image_picker,
             current_image: RefCell::new(None),
+            current_image_url: None,
+            gallery_index: 0,
+            show_meta: false,
+            revealed: HashSet::new(),
+            prefetch_enabled: config.tui.prefetch_comments,
+            prefetch: None,
+            highlighted: None,
+            comment_cache: Vec::new(),
+            wide: false,
+            auto_expand_depth: config.tui.auto_expand_depth,
+            absolute_time: config.tui.absolute_time,
+            plain_markdown: config.tui.plain_markdown,
+            spinner: config.tui.spinner,
         }
     }
 
@@ -95,9 +156,10 @@ impl App {
         self.loading = true;
         self.loading_message = "Loading r/all...".to_string();
         let client = RedditClient::new().await?;
-        match client.get_subreddit_posts("all", "hot", "day", 25).await {
+        match client.get_subreddit_posts("all", "hot", "day", 25, false).await {
             Ok(posts) => {
                 self.home_posts = posts;
+                self.home_deferred = false;
             }
             Err(e) => {
                 self.error_message = Some(format!("Failed to load posts: {}", e));
@@ -107,6 +169,27 @@ impl App {
         Ok(())
     }
 
+    /// Load a subreddit's hot posts along with its about info
+    pub async fn load_subreddit(&mut self, name: &str) -> Result<()> {
+        self.loading = true;
+        self.loading_message = format!("Loading r/{}...", name);
+        let client = RedditClient::new().await?;
+        match client.get_subreddit_posts(name, "hot", "day", 25, false).await {
+            Ok(posts) => {
+                // Info is best-effort; the post list is still useful without it
+                self.current_subreddit = client.get_subreddit_info(name).await.ok();
+                self.subreddit_posts = posts;
+                self.selected_post_index = 0;
+                self.view = View::Subreddit;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load r/{}: {}", name, e));
+            }
+        }
+        self.loading = false;
+        Ok(())
+    }
+
     /// Load an image from URL
     pub async fn load_image(&mut self, url: &str) {
         if let Some(ref picker) = self.image_picker {
@@ -119,6 +202,7 @@ impl App {
                         if let Ok(img) = image::load_from_memory(&bytes) {
                             let protocol = picker.new_resize_protocol(img);
                             *self.current_image.borrow_mut() = Some(protocol);
+                            self.current_image_url = Some(url.to_string());
                         }
                     }
                 }
@@ -129,10 +213,24 @@ impl App {
         }
     }
 
+    /// Pixel size of the PostDetail image panel, as far as the terminal and image
+    /// picker can tell (assumes 80x24 and 8x16 cells when they can't)
+    fn image_panel_pixels(&self) -> (u32, u32) {
+        let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+        let panel = ui::image_panel(self, Rect::new(0, 0, cols, rows));
+        let (font_width, font_height) = self.image_picker.as_ref().map_or((8, 16), |p| p.font_size());
+        (
+            u32::from(panel.width) * u32::from(font_width),
+            u32::from(panel.height) * u32::from(font_height),
+        )
+    }
+
     /// Main event loop
     pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         // Load r/all posts on startup
-        self.load_home_posts().await?;
+        if !self.home_deferred {
+            self.load_home_posts().await?;
+        }
 
         while self.running {
             // Draw UI
@@ -149,14 +247,86 @@ impl App {
                     self.handle_key(key.code, key.modifiers).await?;
                 }
             }
+
+            // The split-pane preview shows the top comment, so it needs the prefetch too
+            self.wide = terminal
+                .size()
+                .is_ok_and(|size| ui::split_pane(size.width));
+            if self.prefetch_enabled || self.wide {
+                self.tick_prefetch().await;
+            }
         }
         Ok(())
     }
 
+    /// Start fetching comments for a post once it has stayed highlighted for
+    /// `PREFETCH_DELAY`, replacing any fetch for a post the cursor has since left,
+    /// and cache the result when it lands
+    async fn tick_prefetch(&mut self) {
+        if self.prefetch.as_ref().is_some_and(|p| p.task.is_finished()) {
+            let Prefetch { post_id, task } = self.prefetch.take().expect("checked above");
+            if let Ok(Ok(comments)) = task.await {
+                if self.comment_cache.len() >= PREFETCH_CACHE_SIZE {
+                    self.comment_cache.remove(0);
+                }
+                self.comment_cache.push((post_id, comments));
+            }
+        }
+
+        let Some(post_id) = self.selected_list_post().map(|p| p.id.clone()) else {
+            self.highlighted = None;
+            return;
+        };
+        let since = match self.highlighted {
+            Some((ref id, since)) if *id == post_id => since,
+            _ => {
+                // The cursor moved on, so an in-flight fetch for another post is stale
+                if let Some(stale) = self.prefetch.take_if(|p| p.post_id != post_id) {
+                    stale.task.abort();
+                }
+                self.highlighted = Some((post_id, Instant::now()));
+                return;
+            }
+        };
+
+        let wanted = since.elapsed() >= PREFETCH_DELAY
+            && !self.comment_cache.iter().any(|(id, _)| *id == post_id)
+            && self.prefetch.is_none(); // any in-flight fetch is already for this post
+        if wanted {
+            let id = post_id.clone();
+            let task = tokio::spawn(async move { Self::fetch_comments(&id).await });
+            self.prefetch = Some(Prefetch { post_id, task });
+        }
+    }
+
+    /// Prefetched comments for a post, if they've arrived
+    pub fn cached_comments(&self, post_id: &str) -> Option<&[CommentSummary]> {
+        self.comment_cache
+            .iter()
+            .find(|(id, _)| id == post_id)
+            .map(|(_, comments)| comments.as_slice())
+    }
+
+    /// Comments for a post being opened: from the prefetch cache, the in-flight
+    /// prefetch, or a fresh request. Cached threads stay cached so the preview
+    /// pane still has them after going back.
+    async fn comments_for_open(&mut self, post_id: &str) -> Result<Vec<CommentSummary>> {
+        if let Some(comments) = self.cached_comments(post_id) {
+            return Ok(comments.to_vec());
+        }
+        if let Some(prefetch) = self.prefetch.take_if(|p| p.post_id == post_id) {
+            if let Ok(result) = prefetch.task.await {
+                return result;
+            }
+        }
+        Self::fetch_comments(post_id).await
+    }
+
     /// Handle keyboard input
     async fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
-        // Clear error on any key press
+        // Clear error and status on any key press
         self.error_message = None;
+        self.status_message = None;
 
         match self.input_mode {
             InputMode::Editing => self.handle_editing_key(key).await?,
@@ -172,30 +342,10 @@ impl App {
                 self.input_mode = InputMode::Normal;
                 self.perform_search().await?;
             }
-            KeyCode::Char(c) => {
-                self.search_input.insert(self.cursor_position, c);
-                self.cursor_position += 1;
-            }
-            KeyCode::Backspace => {
-                if self.cursor_position > 0 {
-                    self.cursor_position -= 1;
-                    self.search_input.remove(self.cursor_position);
-                }
-            }
-            KeyCode::Left => {
-                if self.cursor_position > 0 {
-                    self.cursor_position -= 1;
-                }
-            }
-            KeyCode::Right => {
-                if self.cursor_position < self.search_input.len() {
-                    self.cursor_position += 1;
-                }
-            }
             KeyCode::Esc => {
                 self.input_mode = InputMode::Normal;
             }
-            _ => {}
+            _ => edit_line(&mut self.search_input, &mut self.cursor_position, key),
         }
         Ok(())
     }
@@ -231,6 +381,14 @@ impl App {
                 self.select_item().await?;
             }
 
+            // Quick-jump to a visible row (post lists)
+            KeyCode::Char(c @ '1'..='9') => {
+                if matches!(self.view, View::Home | View::SearchResults | View::Subreddit) {
+                    let row = c as usize - '1' as usize;
+                    self.jump_to_row(row);
+                }
+            }
+
             // Scrolling in post detail
             KeyCode::Char('d') => {
                 if self.view == View::PostDetail {
@@ -244,6 +402,61 @@ impl App {
                 }
             }
 
+            // Refresh the current view
+            KeyCode::Char('r') => {
+                self.refresh().await?;
+            }
+
+            // Browse the selected post's subreddit
+            KeyCode::Char('b') => {
+                if matches!(self.view, View::Home | View::SearchResults) {
+                    let subreddit = self
+                        .selected_list_post()
+                        .map(|p| p.subreddit.clone());
+                    if let Some(subreddit) = subreddit {
+                        self.load_subreddit(&subreddit).await?;
+                    }
+                }
+            }
+
+            // Page through gallery images (in PostDetail view)
+            KeyCode::Left | KeyCode::Right if self.view == View::PostDetail => {
+                self.step_gallery(key == KeyCode::Right).await;
+            }
+
+            // Open image externally (in PostDetail view)
+            KeyCode::Char('i') if self.view == View::PostDetail => {
+                self.open_image_externally();
+            }
+
+            // Reveal/re-hide an NSFW or spoiler post
+            KeyCode::Char('x') => {
+                let id = match self.view {
+                    View::PostDetail => self.current_post.as_ref().map(|p| p.id.clone()),
+                    _ => self.selected_list_post().map(|p| p.id.clone()),
+                };
+                if let Some(id) = id {
+                    if !self.revealed.remove(&id) {
+                        self.revealed.insert(id);
+                    }
+                }
+            }
+
+            // Toggle the routing trace for the last search
+            KeyCode::Char('D') => {
+                self.show_routing = !self.show_routing;
+            }
+
+            // Toggle the full metadata panel (in PostDetail view)
+            KeyCode::Char('m') if self.view == View::PostDetail => {
+                self.show_meta = !self.show_meta;
+            }
+
+            // Open the selected comment in the browser (in PostDetail view)
+            KeyCode::Char('l') if self.view == View::PostDetail => {
+                self.open_comment_link();
+            }
+
             // Sort/time filters (in SearchResults view)
             KeyCode::Char('o') => {
                 if self.view == View::SearchResults {
@@ -273,9 +486,21 @@ impl App {
                 self.search_results = None;
                 self.selected_post_index = 0;
             }
+            View::Subreddit => {
+                self.view = if self.search_results.is_some() {
+                    View::SearchResults
+                } else {
+                    View::Home
+                };
+                self.subreddit_posts.clear();
+                self.current_subreddit = None;
+                self.selected_post_index = 0;
+            }
             View::PostDetail => {
                 // Go back to wherever we came from
-                if self.search_results.is_some() {
+                if !self.subreddit_posts.is_empty() {
+                    self.view = View::Subreddit;
+                } else if self.search_results.is_some() {
                     self.view = View::SearchResults;
                 } else {
                     self.view = View::Home;
@@ -285,6 +510,7 @@ impl App {
                 self.selected_comment_index = 0;
                 self.scroll_offset = 0;
                 *self.current_image.borrow_mut() = None;
+                self.current_image_url = None;
             }
         }
     }
@@ -303,6 +529,11 @@ impl App {
                     }
                 }
             }
+            View::Subreddit => {
+                if self.selected_post_index < self.subreddit_posts.len().saturating_sub(1) {
+                    self.selected_post_index += 1;
+                }
+            }
             View::PostDetail => {
                 let visible_count = self.visible_comments().len();
                 if self.selected_comment_index < visible_count.saturating_sub(1) {
@@ -319,7 +550,7 @@ impl App {
 
     fn move_up(&mut self) {
         match self.view {
-            View::Home | View::SearchResults => {
+            View::Home | View::SearchResults | View::Subreddit => {
                 if self.selected_post_index > 0 {
                     self.selected_post_index -= 1;
                 }
@@ -336,6 +567,33 @@ impl App {
         }
     }
 
+    /// Jump selection to a visible row in the current post list.
+    /// Post lists always render from the top, so visible rows map directly to indices.
+    fn jump_to_row(&mut self, row: usize) {
+        let len = match self.view {
+            View::Home => self.home_posts.len(),
+            View::SearchResults => self.search_results.as_ref().map_or(0, |r| r.posts.len()),
+            View::Subreddit => self.subreddit_posts.len(),
+            View::PostDetail => return,
+        };
+        if len > 0 {
+            self.selected_post_index = row.min(len - 1);
+        }
+    }
+
+    /// The highlighted post in the current list view, if any
+    pub fn selected_list_post(&self) -> Option<&PostSummary> {
+        match self.view {
+            View::Home => self.home_posts.get(self.selected_post_index),
+            View::SearchResults => self
+                .search_results
+                .as_ref()
+                .and_then(|r| r.posts.get(self.selected_post_index)),
+            View::Subreddit => self.subreddit_posts.get(self.selected_post_index),
+            View::PostDetail => None,
+        }
+    }
+
     async fn select_item(&mut self) -> Result<()> {
         // In PostDetail view, Enter toggles comment expansion
         if self.view == View::PostDetail {
@@ -343,28 +601,27 @@ impl App {
             return Ok(());
         }
 
-        let post = match self.view {
-            View::Home => self.home_posts.get(self.selected_post_index).cloned(),
-            View::SearchResults => self
-                .search_results
-                .as_ref()
-                .and_then(|r| r.posts.get(self.selected_post_index).cloned()),
-            View::PostDetail => return Ok(()),
-        };
+        let post = self.selected_list_post().cloned();
 
         if let Some(post) = post {
             self.current_post = Some(post.clone());
             self.loading = true;
             *self.current_image.borrow_mut() = None; // Clear previous image
-
-            // Load image if post has one
-            if let Some(ref image_url) = post.image_url {
+            self.current_image_url = None;
+            self.gallery_index = 0;
+
+            // Load image if post has one (the first one, for galleries), preferring the
+            // smallest preview that still fills the image panel over the full-size source
+            let (width, height) = self.image_panel_pixels();
+            let preview = post.best_fit(width, height).map(|size| &size.url);
+            if let Some(image_url) = preview.or(post.image_url.as_ref()) {
                 self.load_image(image_url).await;
             }
 
-            // Fetch comments
-            match self.fetch_comments(&post.id).await {
-                Ok(comments) => {
+            // Fetch comments (instant if they were prefetched)
+            match self.comments_for_open(&post.id).await {
+                Ok(mut comments) => {
+                    Self::auto_expand(&mut comments, self.auto_expand_depth);
                     self.comments = comments;
                     self.view = View::PostDetail;
                 }
@@ -377,15 +634,91 @@ impl App {
         Ok(())
     }
 
+    /// Show the next/previous gallery image, fetching it only when navigated to
+    async fn step_gallery(&mut self, forward: bool) {
+        let Some(images) = self.current_post.as_ref().map(|p| p.images.clone()) else {
+            return;
+        };
+        if images.len() < 2 {
+            return;
+        }
+
+        self.gallery_index = if forward {
+            (self.gallery_index + 1) % images.len()
+        } else {
+            (self.gallery_index + images.len() - 1) % images.len()
+        };
+
+        *self.current_image.borrow_mut() = None;
+        self.current_image_url = None;
+        self.load_image(&images[self.gallery_index]).await;
+        if self.current_image_url.is_none() {
+            self.status_message = Some(format!(
+                "Couldn't load image {}/{}",
+                self.gallery_index + 1,
+                images.len()
+            ));
+        }
+    }
+
+    /// Open the current post's image in the system viewer/browser
+    fn open_image_externally(&mut self) {
+        let Some(ref shown) = self.current_image_url else {
+            return;
+        };
+        // A downsized preview is shown inline; open the full-size source instead
+        let url = self
+            .current_post
+            .as_ref()
+            .filter(|p| p.thumbnails.iter().any(|size| &size.url == shown))
+            .and_then(|p| p.image_url.as_ref())
+            .unwrap_or(shown);
+        match open::that(url) {
+            Ok(()) => self.status_message = Some("Opened image externally".to_string()),
+            Err(e) => self.error_message = Some(format!("Failed to open image: {}", e)),
+        }
+    }
+
+    /// Whether a post's title/body can be shown (not NSFW/spoiler, or revealed with `x`)
+    pub fn is_revealed(&self, post: &PostSummary) -> bool {
+        !(post.nsfw || post.spoiler) || self.revealed.contains(&post.id)
+    }
+
+    /// Open the selected comment's permalink in the browser
+    fn open_comment_link(&mut self) {
+        let Some(url) = self
+            .visible_comments()
+            .get(self.selected_comment_index)
+            .map(|c| c.permalink.clone())
+        else {
+            return;
+        };
+        if let Err(e) = open::that(&url) {
+            self.error_message = Some(format!("Failed to open comment: {}", e));
+        }
+    }
+
     /// Toggle expansion of the currently selected comment
     fn toggle_comment_expansion(&mut self) {
         if let Some(comment) = self.get_visible_comment_mut(self.selected_comment_index) {
-            if comment.reply_count > 0 {
+            // Collapsed comments toggle too, to show or re-fold their body
+            if comment.reply_count > 0 || comment.collapsed {
                 comment.expanded = !comment.expanded;
             }
         }
     }
 
+    /// Expand comments shallower than `max_depth` so threads open pre-expanded.
+    /// Comments Reddit collapses stay folded, as on the site.
+    fn auto_expand(comments: &mut [CommentSummary], max_depth: u32) {
+        for comment in comments.iter_mut() {
+            if comment.depth < max_depth && comment.reply_count > 0 && !comment.collapsed {
+                comment.expanded = true;
+                Self::auto_expand(&mut comment.replies, max_depth);
+            }
+        }
+    }
+
     /// Get mutable reference to a comment by its visible index
     fn get_visible_comment_mut(&mut self, index: usize) -> Option<&mut CommentSummary> {
         let mut current_index = 0;
@@ -440,7 +773,9 @@ impl App {
         self.error_message = None;
 
         let router = NlpRouter::new();
+        let parse_started = Instant::now();
         let mut params = router.parse_query(&self.search_input).await?;
+        let parse_ms = parse_started.elapsed().as_millis();
 
         // Build debug info
         let method_str = match params.parse_method {
@@ -461,10 +796,29 @@ impl App {
         params.time = self.search_time.clone();
 
         self.loading_message = "Searching Reddit...".to_string();
+        let search_started = Instant::now();
         let client = RedditClient::new().await?;
-        match client.search(&params).await {
+        let result = client.search(&params).await;
+
+        self.routing_trace = Some(serde_json::json!({
+            "input": self.search_input,
+            "method": method_str,
+            "pattern": router.matching_pattern(&self.search_input),
+            // parse_query tries AI whenever no pattern matches; this is the heuristic's view
+            "needs_nlp": router.explain_needs_nlp(&self.search_input),
+            "params": params,
+            "timing_ms": {
+                "parse": parse_ms,
+                "search": search_started.elapsed().as_millis(),
+            },
+        }));
+        self.show_routing = self.explain_routing;
+
+        match result {
             Ok(results) => {
                 self.search_results = Some(results);
+                self.subreddit_posts.clear();
+                self.current_subreddit = None;
                 self.view = View::SearchResults;
                 self.selected_post_index = 0;
             }
@@ -501,8 +855,111 @@ impl App {
         self.perform_search().await
     }
 
-    async fn fetch_comments(&self, post_id: &str) -> Result<Vec<CommentSummary>> {
+    /// Reload the current view, keeping the selection where possible
+    async fn refresh(&mut self) -> Result<()> {
+        let selected_post = self.selected_post_index;
+        match self.view {
+            View::Home => {
+                self.load_home_posts().await?;
+                self.selected_post_index =
+                    selected_post.min(self.home_posts.len().saturating_sub(1));
+            }
+            View::SearchResults => {
+                self.rerun_search().await?;
+                let len = self.search_results.as_ref().map_or(0, |r| r.posts.len());
+                self.selected_post_index = selected_post.min(len.saturating_sub(1));
+            }
+            View::Subreddit => {
+                let name = match (&self.current_subreddit, self.subreddit_posts.first()) {
+                    (Some(sub), _) => sub.name.clone(),
+                    (None, Some(post)) => post.subreddit.clone(),
+                    (None, None) => return Ok(()),
+                };
+                self.load_subreddit(&name).await?;
+                self.selected_post_index =
+                    selected_post.min(self.subreddit_posts.len().saturating_sub(1));
+            }
+            View::PostDetail => {
+                let Some(post_id) = self.current_post.as_ref().map(|p| p.id.clone()) else {
+                    return Ok(());
+                };
+                self.loading = true;
+                self.loading_message = "Reloading comments...".to_string();
+                match Self::fetch_comments(&post_id).await {
+                    Ok(mut comments) => {
+                        Self::auto_expand(&mut comments, self.auto_expand_depth);
+                        self.comments = comments;
+                        let visible = self.visible_comments().len();
+                        self.selected_comment_index =
+                            self.selected_comment_index.min(visible.saturating_sub(1));
+                    }
+                    Err(e) => {
+                        self.error_message = Some(format!("Failed to reload comments: {}", e));
+                    }
+                }
+                self.loading = false;
+            }
+        }
+        Ok(())
+    }
+
+    async fn fetch_comments(post_id: &str) -> Result<Vec<CommentSummary>> {
         let client = RedditClient::new().await?;
-        client.get_comments(post_id, "best", 50).await
+        // Load replies so expand/collapse works
+        client.get_comments(post_id, "best", 50, true, None).await
+    }
+}
+
+/// Typing, Backspace and Left/Right in a one-line input. `cursor` is a byte offset and
+/// always moves by whole characters, so multibyte input (accents, CJK, emoji) is safe.
+fn edit_line(input: &mut String, cursor: &mut usize, key: KeyCode) {
+    let prev = input[..*cursor].chars().next_back().map(char::len_utf8);
+    let next = input[*cursor..].chars().next().map(char::len_utf8);
+    match (key, prev, next) {
+        (KeyCode::Char(c), _, _) => {
+            input.insert(*cursor, c);
+            *cursor += c.len_utf8();
+        }
+        (KeyCode::Backspace, Some(len), _) => {
+            *cursor -= len;
+            input.remove(*cursor);
+        }
+        (KeyCode::Left, Some(len), _) => *cursor -= len,
+        (KeyCode::Right, _, Some(len)) => *cursor += len,
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_line_multibyte() {
+        let mut input = String::new();
+        let mut cursor = 0;
+        for c in "café日本".chars() {
+            edit_line(&mut input, &mut cursor, KeyCode::Char(c));
+        }
+        assert_eq!((input.as_str(), cursor), ("café日本", input.len()));
+
+        // Back over 本 and 日, insert between é and 日
+        edit_line(&mut input, &mut cursor, KeyCode::Left);
+        edit_line(&mut input, &mut cursor, KeyCode::Left);
+        edit_line(&mut input, &mut cursor, KeyCode::Char('🎉'));
+        assert_eq!(input, "café🎉日本");
+
+        edit_line(&mut input, &mut cursor, KeyCode::Backspace);
+        edit_line(&mut input, &mut cursor, KeyCode::Backspace);
+        assert_eq!((input.as_str(), cursor), ("caf日本", 3));
+
+        edit_line(&mut input, &mut cursor, KeyCode::Right);
+        edit_line(&mut input, &mut cursor, KeyCode::Right);
+        edit_line(&mut input, &mut cursor, KeyCode::Right);
+        assert_eq!(cursor, input.len());
+        for _ in 0..10 {
+            edit_line(&mut input, &mut cursor, KeyCode::Backspace);
+        }
+        assert_eq!((input.as_str(), cursor), ("", 0));
     }
 }
@@ -14,7 +14,7 @@ use ratatui::prelude::*;
 use std::io::stdout;
 
 /// Run the TUI application
-pub async fn run() -> Result<()> {
+pub async fn run(explain_routing: bool) -> Result<()> {
     // Setup terminal
     enable_raw_mode().map_err(|e| crate::error::RdtError::Tui(e.to_string()))?;
     let mut stdout = stdout();
@@ -26,6 +26,7 @@ pub async fn run() -> Result<()> {
 
     // Create app and run
     let mut app = App::new();
+    app.explain_routing = explain_routing;
     let result = app.run(&mut terminal).await;
 
     // Restore terminal
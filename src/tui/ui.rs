@@ -7,10 +7,20 @@ use ratatui::{
     Frame,
 };
 use ratatui_image::StatefulImage;
+use std::rc::Rc;
+use unicode_width::UnicodeWidthStr;
 
-/// Main render function
-pub fn render(frame: &mut Frame, app: &App) {
-    let chunks = if app.view == View::Home {
+/// Post lists get a preview pane beside them on terminals at least this wide
+const SPLIT_PANE_MIN_WIDTH: u16 = 140;
+
+/// Whether list views on a terminal `width` columns wide show the preview pane
+pub fn split_pane(width: u16) -> bool {
+    width >= SPLIT_PANE_MIN_WIDTH
+}
+
+/// Top-level layout: [logo,] search bar, main content, status bar
+fn screen_chunks(home: bool, area: Rect) -> Rc<[Rect]> {
+    if home {
         // Home view: logo + search + content + status
         Layout::default()
             .direction(Direction::Vertical)
@@ -20,7 +30,7 @@ pub fn render(frame: &mut Frame, app: &App) {
                 Constraint::Min(0),    // Main content
                 Constraint::Length(1), // Status bar
             ])
-            .split(frame.area())
+            .split(area)
     } else {
         // Other views: search + content + status
         Layout::default()
@@ -30,8 +40,21 @@ pub fn render(frame: &mut Frame, app: &App) {
                 Constraint::Min(0),    // Main content
                 Constraint::Length(1), // Status bar
             ])
-            .split(frame.area())
-    };
+            .split(area)
+    }
+}
+
+/// Where PostDetail will draw the current post's image on a screen of `area`, so a
+/// preview of about that size can be fetched before the view is shown
+pub fn image_panel(app: &App, area: Rect) -> Rect {
+    let main_area = screen_chunks(false, area)[1];
+    let content_area = post_detail_chunks(app, main_area)[1];
+    image_chunks(content_area)[0]
+}
+
+/// Main render function
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = screen_chunks(app.view == View::Home, frame.area());
 
     if app.view == View::Home {
         render_logo(frame, chunks[0]);
@@ -44,6 +67,10 @@ pub fn render(frame: &mut Frame, app: &App) {
         render_status_bar(frame, app, chunks[2]);
     }
 
+    if app.show_routing {
+        render_routing_popup(frame, app.routing_trace.as_ref());
+    }
+
     // Show error popup if present
     if let Some(ref error) = app.error_message {
         render_error_popup(frame, error);
@@ -51,7 +78,7 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     // Show loading indicator
     if app.loading {
-        render_loading(frame, &app.loading_message);
+        render_loading(frame, &app.loading_message, app.spinner.as_deref());
     }
 }
 
@@ -71,19 +98,31 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
         );
     frame.render_widget(input, area);
 
-    // Show cursor when editing
+    // Show cursor when editing, after the columns the text before it takes up
+    // (wide characters like CJK take two)
     if app.input_mode == InputMode::Editing {
-        frame.set_cursor_position((
-            area.x + app.cursor_position as u16 + 1,
-            area.y + 1,
-        ));
+        let column = app.search_input[..app.cursor_position].width();
+        frame.set_cursor_position((area.x + column as u16 + 1, area.y + 1));
     }
 }
 
 fn render_main_content(frame: &mut Frame, app: &App, area: Rect) {
+    // Wide terminals: post list on the left, preview of the highlighted post on the right
+    let area = if app.view != View::PostDetail && split_pane(area.width) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area);
+        render_preview(frame, app, chunks[1]);
+        chunks[0]
+    } else {
+        area
+    };
+
     match app.view {
         View::Home => render_home(frame, app, area),
         View::SearchResults => render_search_results(frame, app, area),
+        View::Subreddit => render_subreddit(frame, app, area),
         View::PostDetail => render_post_detail(frame, app, area),
     }
 }
@@ -107,12 +146,17 @@ fn render_logo(frame: &mut Frame, area: Rect) {
 
 fn render_home(frame: &mut Frame, app: &App, area: Rect) {
     // Posts list (logo is rendered separately above search bar)
-    if app.home_posts.is_empty() {
+    if app.home_deferred {
+        let prompt = Paragraph::new("  Press / to search, or r to load r/all")
+            .style(Style::default().fg(Color::Rgb(128, 128, 128)))
+            .block(Block::default().borders(Borders::ALL).title(" rdt "));
+        frame.render_widget(prompt, area);
+    } else if app.home_posts.is_empty() {
         let loading = Paragraph::new("  Loading r/all...")
             .block(Block::default().borders(Borders::ALL).title(" r/all "));
         frame.render_widget(loading, area);
     } else {
-        render_post_list(frame, &app.home_posts, app.selected_post_index, " r/all - Hot ", area);
+        render_post_list(frame, app, &app.home_posts, " r/all - Hot ", area);
     }
 }
 
@@ -155,17 +199,65 @@ fn render_search_results(frame: &mut Frame, app: &App, area: Rect) {
         None => " Results ".to_string(),
     };
 
-    render_post_list(frame, posts, app.selected_post_index, &title, chunks[1]);
+    render_post_list(frame, app, posts, &title, chunks[1]);
+}
+
+fn render_subreddit(frame: &mut Frame, app: &App, area: Rect) {
+    let (list_area, title) = if let Some(ref sub) = app.current_subreddit {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6), Constraint::Min(0)])
+            .split(area);
+
+        let header_text = vec![
+            Line::from(Span::styled(
+                &sub.title,
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(vec![
+                Span::styled(
+                    format!("{} subscribers", sub.subscribers),
+                    Style::default().fg(Color::Rgb(255, 139, 61)),
+                ),
+                Span::raw(match sub.active_users {
+                    Some(active) => format!(" | {} online", active),
+                    None => String::new(),
+                }),
+            ]),
+            Line::from(Span::raw(sub.description.as_str())),
+        ];
+
+        let header = Paragraph::new(header_text)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" r/{} ", sub.name)),
+            )
+            .wrap(Wrap { trim: true });
+        frame.render_widget(header, chunks[0]);
+
+        (chunks[1], format!(" r/{} - Hot ", sub.name))
+    } else {
+        let name = app
+            .subreddit_posts
+            .first()
+            .map(|p| p.subreddit.as_str())
+            .unwrap_or_default();
+        (area, format!(" r/{} - Hot ", name))
+    };
+
+    render_post_list(frame, app, &app.subreddit_posts, &title, list_area);
 }
 
 /// Shared post list renderer
 fn render_post_list(
     frame: &mut Frame,
+    app: &App,
     posts: &[crate::api::models::PostSummary],
-    selected_index: usize,
     title: &str,
     area: Rect,
 ) {
+    let selected_index = app.selected_post_index;
     let items: Vec<ListItem> = posts
         .iter()
         .enumerate()
@@ -192,7 +284,7 @@ fn render_post_list(
                     format!("{:<4} ", age),
                     Style::default().fg(Color::Rgb(100, 100, 100)), // Gray for age
                 ),
-                Span::raw(&post.title),
+                post_title_span(app, post),
             ]);
 
             ListItem::new(content).style(style)
@@ -206,32 +298,131 @@ fn render_post_list(
     frame.render_widget(list, area);
 }
 
-fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
-    let has_image = app.current_image.borrow().is_some();
+/// Title, or a red `[NSFW] <hidden>` / `[SPOILER] <hidden>` mask until revealed with `x`
+fn post_title_span<'a>(app: &App, post: &'a crate::api::models::PostSummary) -> Span<'a> {
+    let mask = if app.is_revealed(post) {
+        None
+    } else if post.nsfw {
+        Some("[NSFW] <hidden>")
+    } else if post.spoiler {
+        Some("[SPOILER] <hidden>")
+    } else {
+        None
+    };
+    match mask {
+        Some(mask) => Span::styled(mask, Style::default().fg(Color::Red)),
+        None => Span::raw(&post.title),
+    }
+}
 
+/// Split-pane preview: the highlighted post's body and, once prefetched, its top comment
+fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(" Preview ");
+    let Some(post) = app.selected_list_post() else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let dim = Style::default().fg(Color::Rgb(128, 128, 128));
+
+    let mut text = vec![
+        Line::from(post_title_span(app, post).patch_style(Modifier::BOLD)),
+        Line::from(vec![
+            Span::styled(
+                format!("u/{}", post.author),
+                Style::default().fg(Color::Rgb(100, 149, 237)),
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                format!("{} pts", post.score),
+                Style::default().fg(Color::Rgb(255, 139, 61)),
+            ),
+            Span::raw(format!(" | {} comments", post.num_comments)),
+        ]),
+        Line::from(""),
+    ];
+
+    if !app.is_revealed(post) {
+        text.push(Line::from(Span::styled("Hidden; press x to reveal", dim)));
+    } else {
+        match (&post.selftext, &post.link_url) {
+            (Some(body), _) => text.extend(body.lines().map(|l| Line::from(l.to_string()))),
+            (None, Some(url)) => text.push(Line::from(Span::styled(url.clone(), dim))),
+            (None, None) => text.push(Line::from(Span::styled("(no text)", dim))),
+        }
+
+        text.push(Line::from(""));
+        let top = app
+            .cached_comments(&post.id)
+            .map(|comments| comments.iter().find(|c| !c.stickied));
+        match top {
+            Some(Some(comment)) => {
+                text.push(Line::from(vec![
+                    Span::styled("Top comment ", dim),
+                    Span::styled(
+                        format!("u/{}", comment.author),
+                        Style::default().fg(Color::Rgb(100, 149, 237)),
+                    ),
+                    Span::styled(
+                        format!(" {} pts", comment.score),
+                        Style::default().fg(Color::Rgb(255, 139, 61)),
+                    ),
+                ]));
+                text.extend(comment.body.lines().map(|l| Line::from(comment_body_spans(app, l))));
+            }
+            Some(None) => text.push(Line::from(Span::styled("No comments yet", dim))),
+            None if post.num_comments > 0 => {
+                text.push(Line::from(Span::styled("Loading top comment...", dim)))
+            }
+            None => {}
+        }
+    }
+
+    let preview = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    frame.render_widget(preview, area);
+}
+
+/// PostDetail layout: header at top, then content below
+fn post_detail_chunks(app: &App, area: Rect) -> Rc<[Rect]> {
     // Calculate header height based on whether post has body
-    let header_height = if app.current_post.as_ref().and_then(|p| p.selftext.as_ref()).is_some() {
+    let mut header_height = if app.current_post.as_ref().and_then(|p| p.selftext.as_ref()).is_some() {
         12 // More room for posts with body
     } else {
         5  // Compact for title-only posts
     };
+    if app.show_meta {
+        header_height += META_LINES + 1;
+    }
 
-    // Header at top, then content below
-    let main_chunks = Layout::default()
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(header_height),
             Constraint::Min(0), // Content area
         ])
-        .split(area);
+        .split(area)
+}
+
+/// Split PostDetail content into image and comments
+fn image_chunks(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(50), // Image (half the content area)
+            Constraint::Percentage(50), // Comments
+        ])
+        .split(area)
+}
+
+fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let masked = app.current_post.as_ref().is_some_and(|p| !app.is_revealed(p));
+    let has_image = app.current_image.borrow().is_some() && !masked;
+
+    let main_chunks = post_detail_chunks(app, area);
 
     // Post header with body
     if let Some(ref post) = app.current_post {
         let mut header_text = vec![
-            Line::from(Span::styled(
-                &post.title,
-                Style::default().add_modifier(Modifier::BOLD),
-            )),
+            Line::from(post_title_span(app, post).patch_style(Modifier::BOLD)),
             Line::from(vec![
                 Span::styled(
                     format!("r/{}", post.subreddit),
@@ -248,17 +439,31 @@ fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
                     Style::default().fg(Color::Rgb(255, 139, 61)),
                 ),
                 Span::raw(format!(" | {} comments", post.num_comments)),
+                Span::styled(
+                    format!(" | {}", format_detail_time(app, post.created_utc)),
+                    Style::default().fg(Color::Rgb(100, 100, 100)),
+                ),
             ]),
         ];
 
-        // Add post body if it exists
-        if let Some(ref body) = post.selftext {
+        if app.show_meta {
+            header_text.push(Line::from(""));
+            header_text.extend(post_meta_lines(app, post));
+        }
+
+        // Add post body if it exists (hidden along with the title until revealed)
+        if let (Some(ref body), false) = (&post.selftext, masked) {
             header_text.push(Line::from("")); // blank line
             header_text.push(Line::from(Span::raw(body.as_str())));
         }
 
+        let title = if post.images.len() > 1 {
+            format!(" Post - image {}/{} ", app.gallery_index + 1, post.images.len())
+        } else {
+            " Post ".to_string()
+        };
         let header = Paragraph::new(header_text)
-            .block(Block::default().borders(Borders::ALL).title(" Post "))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .wrap(Wrap { trim: true });
         frame.render_widget(header, main_chunks[0]);
     }
@@ -266,13 +471,7 @@ fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
     // Content area: image on top (if present), comments below
     let content_area = main_chunks[1];
     let comments_area = if has_image {
-        let content_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(50), // Image (half the content area)
-                Constraint::Percentage(50), // Comments
-            ])
-            .split(content_area);
+        let content_chunks = image_chunks(content_area);
 
         // Render image
         let mut image_state = app.current_image.borrow_mut();
@@ -314,7 +513,7 @@ fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
                 String::new()
             };
 
-            let age = format_age(comment.created_utc);
+            let age = format_detail_time(app, comment.created_utc);
             let lines = vec![
                 Line::from(vec![
                     Span::raw(indent.clone()),
@@ -322,6 +521,7 @@ fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
                         format!("u/{}", comment.author),
                         Style::default().fg(Color::Rgb(100, 149, 237)),
                     ),
+                    comment_badges(comment),
                     Span::raw(" "),
                     Span::styled(
                         format!("{} pts", comment.score),
@@ -337,10 +537,20 @@ fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
                         Style::default().fg(Color::Rgb(100, 100, 100)),
                     ),
                 ]),
-                Line::from(vec![
-                    Span::raw(indent),
-                    Span::raw(comment.body.replace('\n', " ")), // Full length, just collapse newlines
-                ]),
+                Line::from(if comment.collapsed && !comment.expanded {
+                    vec![
+                        Span::raw(indent),
+                        Span::styled(
+                            "[collapsed] Enter to show",
+                            Style::default().fg(Color::Rgb(100, 100, 100)),
+                        ),
+                    ]
+                } else {
+                    // Full length, just collapse newlines
+                    let mut spans = vec![Span::raw(indent)];
+                    spans.extend(comment_body_spans(app, &comment.body.replace('\n', " ")));
+                    spans
+                }),
                 Line::from(""),
             ];
 
@@ -360,11 +570,132 @@ fn render_post_detail(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(comments_list, comments_area);
 }
 
+/// A comment body as spans: styled markdown, or the raw text with `tui.plain_markdown`.
+/// Link URLs are shown while the metadata panel (`m`) is open.
+fn comment_body_spans(app: &App, body: &str) -> Vec<Span<'static>> {
+    if app.plain_markdown {
+        return vec![Span::raw(body.to_string())];
+    }
+    markdown_spans(body, app.show_meta)
+}
+
+/// Light markdown for comments: `[text](url)` becomes underlined `text` (followed by
+/// the URL when `show_urls`), `**bold**` bold and `*italic*`/`_italic_` italic.
+/// Anything else, including unclosed markers, is kept as typed.
+fn markdown_spans(text: &str, show_urls: bool) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let prev = text[..i].chars().next_back();
+        if let Some((styled, len)) = markdown_token(&text[i..], prev, show_urls) {
+            if !plain.is_empty() {
+                spans.push(Span::raw(std::mem::take(&mut plain)));
+            }
+            spans.extend(styled);
+            i += len;
+        } else {
+            plain.push(c);
+            i += c.len_utf8();
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
+
+/// The styled spans for a link or emphasis at the start of `rest`, and how many bytes it took
+fn markdown_token(
+    rest: &str,
+    prev: Option<char>,
+    show_urls: bool,
+) -> Option<(Vec<Span<'static>>, usize)> {
+    if let Some(inner) = rest.strip_prefix('[') {
+        let label_end = inner.find("](")?;
+        let label = &inner[..label_end];
+        let url_start = label_end + 2;
+        let url_len = inner[url_start..].find(')')?;
+        let url = &inner[url_start..url_start + url_len];
+        if label.is_empty() || label.contains(']') || url.is_empty() || url.contains(' ') {
+            return None;
+        }
+        let mut spans = vec![Span::styled(
+            label.to_string(),
+            Style::default()
+                .fg(Color::Rgb(100, 149, 237))
+                .add_modifier(Modifier::UNDERLINED),
+        )];
+        if show_urls {
+            spans.push(Span::styled(
+                format!(" ({})", url),
+                Style::default().fg(Color::Rgb(100, 100, 100)),
+            ));
+        }
+        return Some((spans, 1 + url_start + url_len + 1));
+    }
+
+    if let Some(inner) = rest.strip_prefix("**") {
+        let end = inner.find("**").filter(|&end| end > 0)?;
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        return Some((vec![Span::styled(inner[..end].to_string(), bold)], end + 4));
+    }
+
+    let marker = rest.chars().next().filter(|&c| c == '*' || c == '_')?;
+    let inner = &rest[1..];
+    // Not emphasis: `_` inside a word (snake_case), a `* item` bullet, or an unclosed `**`
+    if prev.is_some_and(|p| p.is_alphanumeric() || p == marker)
+        || inner.starts_with(|c: char| c.is_whitespace() || c == marker)
+    {
+        return None;
+    }
+    let end = inner.find(marker).filter(|&end| end > 0)?;
+    let italic = Style::default().add_modifier(Modifier::ITALIC);
+    Some((vec![Span::styled(inner[..end].to_string(), italic)], end + 2))
+}
+
+/// " MOD", " ADMIN", " OP" and/or " PINNED" after a comment's author
+fn comment_badges(comment: &crate::api::models::CommentSummary) -> Span<'static> {
+    let mut badges = String::new();
+    match comment.distinguished.as_deref() {
+        Some("moderator") => badges.push_str(" MOD"),
+        Some("admin") => badges.push_str(" ADMIN"),
+        _ => {}
+    }
+    if comment.is_submitter {
+        badges.push_str(" OP");
+    }
+    if comment.stickied {
+        badges.push_str(" PINNED");
+    }
+    Span::styled(
+        badges,
+        Style::default()
+            .fg(Color::Rgb(80, 200, 120))
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let status = match app.view {
-        View::Home => "j/k: Navigate | Enter: View | /: Search | q: Quit",
-        View::SearchResults => "j/k: Nav | Enter: View | o: Sort | t: Time | /: Search | q: Back",
-        View::PostDetail => "j/k: Navigate | Enter: Expand | d/u: Scroll | q/Esc: Back",
+        View::Home => {
+            "j/k: Navigate | 1-9: Jump | Enter: View | b: Subreddit | x: Reveal | r: Refresh | /: Search | q: Quit"
+        }
+        View::SearchResults => {
+            "j/k: Nav | 1-9: Jump | Enter: View | b: Sub | r: Refresh | o: Sort | t: Time | D: Routing | /: Search | q: Back"
+        }
+        View::Subreddit => {
+            "j/k: Navigate | 1-9: Jump | Enter: View | x: Reveal | r: Refresh | /: Search | q/Esc: Back"
+        }
+        View::PostDetail if app.current_post.as_ref().is_some_and(|p| p.images.len() > 1) => {
+            "j/k: Nav | Enter: Expand | d/u: Scroll | ←/→: Image | i: Open image | l: Link | m: Meta | q: Back"
+        }
+        View::PostDetail if app.current_image_url.is_some() => {
+            "j/k: Nav | Enter: Expand | d/u: Scroll | i: Open image | l: Link | m: Meta | r: Refresh | q: Back"
+        }
+        View::PostDetail => {
+            "j/k: Navigate | Enter: Expand | d/u: Scroll | l: Open comment | m: Metadata | r: Refresh | q/Esc: Back"
+        }
     };
 
     let mode_indicator = match app.input_mode {
@@ -372,12 +703,35 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         InputMode::Editing => "[EDITING] ",
     };
 
-    let text = format!("{}{}", mode_indicator, status);
+    let text = match app.status_message {
+        Some(ref message) => format!("{}{}", mode_indicator, message),
+        None => format!("{}{}", mode_indicator, status),
+    };
     let paragraph = Paragraph::new(text)
         .style(Style::default().bg(Color::Rgb(30, 30, 30)).fg(Color::Rgb(180, 180, 180)));
     frame.render_widget(paragraph, area);
 }
 
+/// Pretty-printed routing trace of the last search (toggled with D)
+fn render_routing_popup(frame: &mut Frame, trace: Option<&serde_json::Value>) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let text = match trace {
+        Some(trace) => serde_json::to_string_pretty(trace).unwrap_or_default(),
+        None => "No search yet".to_string(),
+    };
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Routing (D to close) "),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
 fn render_error_popup(frame: &mut Frame, error: &str) {
     let area = centered_rect(60, 20, frame.area());
     frame.render_widget(Clear, area);
@@ -404,18 +758,75 @@ fn render_error_popup(frame: &mut Frame, error: &str) {
     frame.render_widget(paragraph, area);
 }
 
-fn render_loading(frame: &mut Frame, message: &str) {
+/// Number of lines `post_meta_lines` produces
+const META_LINES: u16 = 5;
+
+/// Everything else we know about a post, for the `m` metadata panel
+fn post_meta_lines(app: &App, post: &crate::api::models::PostSummary) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::Rgb(128, 128, 128));
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![Span::styled(label, dim), Span::raw(value)])
+    };
+
+    let flags: Vec<&str> = [
+        (post.nsfw, "NSFW"),
+        (post.spoiler, "spoiler"),
+        (post.stickied, "stickied"),
+        (post.locked, "locked"),
+    ]
+    .iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, name)| *name)
+    .collect();
+
+    vec![
+        field(
+            "Upvoted: ",
+            format!(
+                "{:.0}% | Awards: {} | Flair: {}",
+                post.upvote_ratio * 100.0,
+                post.awards,
+                post.flair.as_deref().unwrap_or("-")
+            ),
+        ),
+        field(
+            "Flags: ",
+            if flags.is_empty() { "-".to_string() } else { flags.join(", ") },
+        ),
+        field("Posted: ", format_detail_time(app, post.created_utc)),
+        field("URL: ", post.link_url.clone().unwrap_or_else(|| post.url.clone())),
+        field(
+            "ID: ",
+            format!("{} | Type: {}", post.fullname, post.post_hint.as_deref().unwrap_or("-")),
+        ),
+    ]
+}
+
+/// Spinner frames for the `tui.spinner` setting; unknown names get braille
+fn spinner_frames(style: Option<&str>) -> &'static [&'static str] {
+    match style {
+        Some("none") => &[],
+        Some("dots") => &[".  ", ".. ", "...", " ..", "  .", "   "],
+        Some("line") => &["|", "/", "-", "\\"],
+        _ => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+    }
+}
+
+fn render_loading(frame: &mut Frame, message: &str, spinner: Option<&str>) {
     let area = centered_rect(40, 5, frame.area());
     frame.render_widget(Clear, area);
 
     // Simple spinner using frame count (approximated by time)
-    let spinners = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-    let idx = (std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_millis() / 100) as usize % spinners.len();
-
-    let text = format!("{} {}", spinners[idx], message);
+    let spinners = spinner_frames(spinner);
+    let text = if spinners.is_empty() {
+        message.to_string()
+    } else {
+        let idx = (std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() / 100) as usize % spinners.len();
+        format!("{} {}", spinners[idx], message)
+    };
     let paragraph = Paragraph::new(text)
         .block(Block::default().borders(Borders::ALL).title(" Loading "))
         .style(Style::default().fg(Color::Rgb(100, 149, 237))); // Cornflower blue
@@ -443,18 +854,26 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-/// Truncate comment body for display
-fn truncate_comment(s: &str, max_len: usize) -> String {
-    let s = s.replace('\n', " ");
-    if s.len() > max_len {
-        format!("{}...", &s[..max_len])
-    } else {
-        s
+/// Timestamp for the post detail view: local date/time if `tui.absolute_time`, else relative age
+fn format_detail_time(app: &App, created_utc: Option<f64>) -> String {
+    if !app.absolute_time {
+        return format_age(created_utc);
     }
+    created_utc
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "?".to_string())
 }
 
-/// Format a timestamp as relative age (e.g., "2h", "3d", "1w")
-fn format_age(created_utc: f64) -> String {
+/// Format a timestamp as relative age (e.g., "2h", "3d", "1w"), or "?" if unknown
+fn format_age(created_utc: Option<f64>) -> String {
+    let Some(created_utc) = created_utc else {
+        return "?".to_string();
+    };
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()